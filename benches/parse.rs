@@ -0,0 +1,66 @@
+//! Parse times of the line-oriented days on large generated inputs, and what day 3 saves by
+//! borrowing its banks through [`ZeroCopy`] instead of copying them out of the input.
+//!
+//! Run with `cargo bench --bench parse`.
+
+use aoc2025::generate::{Generator, Rng};
+use aoc2025::{Puzzle, ZeroCopy, day01, day02, day03, day05, day08, day09, input};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const RUNS: usize = 20;
+
+/// A normalized generated input of `size`.
+fn text<P: Puzzle + Generator>(size: usize) -> String {
+    input::normalize(&P::generate(&mut Rng::new(0), size), P::LAYOUT)
+}
+
+/// The fastest of several runs of `parse`.
+fn fastest<'a, T>(text: &'a str, parse: impl Fn(&'a [u8]) -> aoc2025::Result<T>) -> Duration {
+    let mut best = Duration::MAX;
+
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        black_box(parse(black_box(text.as_bytes())).unwrap());
+        best = best.min(start.elapsed());
+    }
+
+    best
+}
+
+fn report(name: &str, text: &str, best: Duration) {
+    println!(
+        "{name}: {best:>10.2?} for {:>6} KiB ({:.0} MB/s)",
+        text.len() / 1024,
+        text.len() as f64 / best.as_secs_f64() / 1e6
+    );
+}
+
+/// Parses a generated input of `size` repeatedly and reports the fastest run.
+fn bench<P: Puzzle + Generator>(name: &str, size: usize) {
+    let text = text::<P>(size);
+    report(name, &text, fastest(&text, P::parse_input));
+}
+
+/// Like [`bench`], then the same with the borrowing parser, and how much faster it is.
+fn bench_borrowed<P: ZeroCopy + Generator>(name: &str, size: usize) {
+    let text = text::<P>(size);
+    let owned = fastest(&text, P::parse_input);
+    let borrowed = fastest(&text, P::parse_borrowed);
+
+    report(name, &text, owned);
+    report(&format!("{name} borrowed"), &text, borrowed);
+    println!(
+        "{name} borrowed is {:.1}x as fast",
+        owned.as_secs_f64() / borrowed.as_secs_f64()
+    );
+}
+
+fn main() {
+    bench::<day01::One>("day01", 200_000);
+    bench::<day02::One>("day02", 20_000);
+    bench_borrowed::<day03::One>("day03", 20_000);
+    bench::<day05::One>("day05", 100_000);
+    bench::<day08::One>("day08", 100_000);
+    bench::<day09::One>("day09", 100_000);
+}
@@ -0,0 +1,53 @@
+//! With the `embed-inputs` feature, generates the table of puzzle inputs compiled into the
+//! binary from every `inputs/<day>/input` present at build time.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn main() {
+    println!("cargo::rerun-if-changed=build.rs");
+
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_none() {
+        return;
+    }
+
+    let root = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap());
+    let inputs = root.join("inputs");
+    println!("cargo::rerun-if-changed={}", inputs.display());
+
+    let mut days: Vec<String> = match fs::read_dir(&inputs) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect(),
+        Err(_) => {
+            println!("cargo::warning=no inputs directory, so every input will be read at runtime");
+            Vec::new()
+        }
+    };
+    days.sort();
+
+    let mut table = String::from("pub const INPUTS: &[(&str, &str)] = &[\n");
+
+    for day in days {
+        let path = inputs.join(&day).join("input");
+        println!("cargo::rerun-if-changed={}", path.display());
+
+        if path.is_file() {
+            table.push_str(&format!(
+                "    ({:?}, include_str!({:?})),\n",
+                format!("inputs/{day}/input"),
+                path.display().to_string()
+            ));
+        } else {
+            println!("cargo::warning=inputs/{day}/input is missing, so it will be read at runtime");
+        }
+    }
+
+    table.push_str("];\n");
+
+    let out = Path::new(&env::var_os("OUT_DIR").unwrap()).join("inputs.rs");
+    fs::write(out, table).unwrap();
+}
@@ -0,0 +1,60 @@
+//! Builds the solvers without `std`, checking that parsing and solving need only `core` and
+//! `alloc`.
+//!
+//! Check it with `cargo nostd`, which builds it on its own for `thumbv7em-none-eabi`, a target
+//! without `std`. A workspace-wide build unifies features, which turns `std` back on for the
+//! main crate, and on a hosted target `std` is always there to link against. Its tests run as
+//! part of `cargo test --workspace`, so run both.
+
+#![no_std]
+
+use aoc2025::{Puzzle, Result, input};
+
+/// Normalizes, parses and solves a whole input, like the runner does with the real one.
+pub fn solve_text<P: Puzzle>(mut puzzle: P, text: &str) -> Result<P::Output> {
+    let text = input::normalize(text, P::LAYOUT);
+    let input = P::parse_input(text.as_bytes())?;
+
+    puzzle.solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2025::{day01, day02, day03, day04, day05, day06, day07, day08, day09};
+
+    macro_rules! examples {
+        ($($day:ident::$part:ident),*) => {
+            $(
+                assert_eq!(
+                    solve_text($day::$part, $day::EXAMPLE).unwrap(),
+                    $day::$part::example_output()
+                );
+            )*
+        };
+    }
+
+    #[test]
+    fn examples() {
+        // Day 8's first part connects a thousand pairs, but only ten for the example.
+        examples!(
+            day01::One,
+            day01::Two,
+            day02::One,
+            day02::Two,
+            day03::One,
+            day03::Two,
+            day04::One,
+            day04::Two,
+            day05::One,
+            day05::Two,
+            day06::One,
+            day06::Two,
+            day07::One,
+            day07::Two,
+            day08::Two,
+            day09::One,
+            day09::Two
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Turning real puzzle inputs, which can't be published, into shareable fixtures that are still
+//! valid and look alike but hold different values.
+
+use crate::generate::Rng;
+use crate::input;
+#[cfg(feature = "std")]
+use crate::registry::Day;
+use crate::{Puzzle, Result};
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
+
+pub trait Anonymizer {
+    /// Rewrite a normalized input. The result must still parse, and should keep its shape,
+    /// e.g. the number of lines or the layout of a grid, while changing the values.
+    fn anonymize(text: &str, rng: &mut Rng) -> Result<String>;
+}
+
+/// Normalizes `text` the way `P`'s real input is, then anonymizes it.
+pub fn anonymize_text<P: Puzzle + Anonymizer>(text: &str, rng: &mut Rng) -> Result<String> {
+    let text = input::normalize(text, P::LAYOUT);
+    P::anonymize(&text, rng)
+}
+
+#[cfg(feature = "std")]
+pub fn fixture_dir(day: u32) -> PathBuf {
+    Path::new("fixtures").join(format!("day{day:02}"))
+}
+
+/// Anonymizes the day's input into [`fixture_dir`], next to its answers in the same form as
+/// imported example answers.
+#[cfg(feature = "std")]
+pub fn write_fixture(day: &Day, seed: u64) -> Result<()> {
+    let text = input::read((day.input_file)())?;
+    let anonymized = (day.anonymize)(&text, &mut Rng::new(seed))?;
+
+    let mut answers = String::new();
+
+    for part in 1..=2 {
+        let imp = day.implementations(part).next().ok_or("no solver")?;
+        answers.push_str(&format!("{part} {}\n", (imp.solve)(&anonymized)?));
+    }
+
+    let dir = fixture_dir(day.number);
+    std::fs::create_dir_all(&dir)?;
+
+    for (name, contents) in [("input", &anonymized), ("answers", &answers)] {
+        let path = dir.join(name);
+        std::fs::write(&path, contents)?;
+        println!("wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::input::Layout;
+    use crate::registry::DAYS;
+
+    /// The width shared by every line, if they all have the same.
+    fn grid_width(text: &str) -> Option<usize> {
+        let mut widths = text.lines().map(str::len);
+        let first = widths.next()?;
+        widths.all(|width| width == first).then_some(first)
+    }
+
+    #[test]
+    fn anonymized_examples_are_valid() -> Result<()> {
+        for day in DAYS {
+            let example = input::normalize(day.example, Layout::Lines);
+
+            for seed in 0..4 {
+                let text = (day.anonymize)(day.example, &mut Rng::new(seed))?;
+                let context = format!("day {} seed {seed}", day.number);
+
+                for imp in day.implementations {
+                    (imp.solve)(&text).map_err(|e| format!("{context} part {}: {e}", imp.part))?;
+                }
+
+                assert_eq!(text.lines().count(), example.lines().count(), "{context}");
+                assert_ne!(text, example, "{context}");
+
+                if let Some(width) = grid_width(&example) {
+                    assert_eq!(grid_width(&text), Some(width), "{context}");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Every fixture still produces the answers written next to it.
+    #[test]
+    fn fixtures() -> Result<()> {
+        for day in DAYS {
+            let dir = fixture_dir(day.number);
+            let text = input::read(&dir.join("input").to_string_lossy())
+                .map_err(|e| format!("{e}; write it with `aoc2025 anonymize {}`", day.number))?;
+            let answers = input::read(&dir.join("answers").to_string_lossy())?;
+
+            for imp in day.implementations {
+                let expected: String = input::example_answer(&answers, imp.part);
+                assert_eq!(
+                    (imp.solve)(&text)?,
+                    expected,
+                    "day {} part {} ({})",
+                    day.number,
+                    imp.part,
+                    imp.name
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+//! Minimal command line handling: options are taken out by name first, whatever remains is
+//! read positionally.
+
+use aoc2025::Result;
+use std::str::FromStr;
+
+pub struct Args(Vec<String>);
+
+impl Args {
+    pub fn from_env() -> Self {
+        Self(std::env::args().skip(1).collect())
+    }
+
+    /// Remove `name` if present, e.g. `--cached`.
+    pub fn flag(&mut self, name: &str) -> bool {
+        match self.0.iter().position(|arg| arg == name) {
+            Some(idx) => {
+                self.0.remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove `name` and the value following it, e.g. `--seed 42`.
+    pub fn option<T>(&mut self, name: &str) -> Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: std::error::Error + 'static,
+    {
+        let Some(idx) = self.0.iter().position(|arg| arg == name) else {
+            return Ok(None);
+        };
+
+        if idx + 1 >= self.0.len() {
+            return Err(format!("{name} needs a value").into());
+        }
+
+        let value = self.0.remove(idx + 1);
+        self.0.remove(idx);
+
+        Ok(Some(value.parse()?))
+    }
+
+    pub fn positional(&mut self) -> Option<String> {
+        if self.0.is_empty() {
+            None
+        } else {
+            Some(self.0.remove(0))
+        }
+    }
+
+    /// Fail on any arguments that no one asked for.
+    pub fn finish(self) -> Result<()> {
+        match self.0.first() {
+            Some(arg) => Err(format!("unexpected argument: {arg}").into()),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Args {
+        Args(s.split_whitespace().map(str::to_owned).collect())
+    }
+
+    #[test]
+    fn options_before_positionals() -> Result<()> {
+        let mut args = args("gen 4 --size 20 --seed 7 --cached");
+
+        assert_eq!(args.option::<u64>("--seed")?, Some(7));
+        assert_eq!(args.option::<usize>("--size")?, Some(20));
+        assert_eq!(args.option::<usize>("--other")?, None);
+        assert!(!args.flag("--no-cache"));
+        assert!(args.flag("--cached"));
+        assert_eq!(args.positional().as_deref(), Some("gen"));
+        assert_eq!(args.positional().as_deref(), Some("4"));
+        args.finish()
+    }
+
+    #[test]
+    fn leftovers() {
+        let mut args = args("check 1 2");
+        args.positional();
+        args.positional();
+        assert!(args.finish().is_err());
+
+        assert!(self::args("gen --seed").option::<u64>("--seed").is_err());
+    }
+}
@@ -0,0 +1,311 @@
+//! Boolean grids packed 64 cells to a word, for counting neighbours a word at a time.
+
+use crate::Result;
+use crate::encode::{Decode, Encode, Reader};
+use crate::grid::Grid;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+const BITS: usize = u64::BITS as usize;
+
+/// Rows start on a word boundary; bits past the width are always clear, so shifts pull in
+/// zeroes at the edges.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    stride: usize,
+    words: Vec<u64>,
+}
+
+/// Adds one bit to each of 64 four-bit counters stored bit-sliced, least significant first.
+fn add(counter: &mut [u64; 4], bits: u64) {
+    let mut carry = bits;
+
+    for slice in counter.iter_mut() {
+        let next = *slice & carry;
+        *slice ^= carry;
+        carry = next;
+    }
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        let stride = width.div_ceil(BITS);
+
+        Self {
+            width,
+            height,
+            stride,
+            words: vec![0; stride * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index(&self, x: usize, y: usize) -> (usize, u64) {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) out of bounds for {}x{} grid",
+            self.width,
+            self.height
+        );
+
+        (y * self.stride + x / BITS, 1 << (x % BITS))
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        if x >= self.width || y >= self.height {
+            return false;
+        }
+
+        let (word, bit) = self.index(x, y);
+        self.words[word] & bit != 0
+    }
+
+    pub fn set(&mut self, x: usize, y: usize) {
+        let (word, bit) = self.index(x, y);
+        self.words[word] |= bit;
+    }
+
+    pub fn clear(&mut self, x: usize, y: usize) {
+        let (word, bit) = self.index(x, y);
+        self.words[word] &= !bit;
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Positions of the set cells, row by row.
+    pub fn ones(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, word)| {
+            let (y, base) = (i / self.stride, i % self.stride * BITS);
+            let mut word = *word;
+
+            core::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+
+                let x = base + word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some((x, y))
+            })
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    /// Clears every cell set in `other`, which must have the same dimensions.
+    pub fn clear_all(&mut self, other: &Self) {
+        assert_eq!((self.width, self.height), (other.width, other.height));
+
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word &= !other;
+        }
+    }
+
+    fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.stride..(y + 1) * self.stride]
+    }
+
+    /// The cells that are set and have fewer than `n` of their eight neighbours set.
+    pub fn set_with_fewer_neighbors(&self, n: u32) -> Self {
+        assert!(n <= 8, "a cell has at most eight neighbours");
+
+        let mut out = Self::new(self.width, self.height);
+        let empty = vec![0; self.stride];
+
+        for y in 0..self.height {
+            let above = if y > 0 { self.row(y - 1) } else { &empty };
+            let below = if y + 1 < self.height {
+                self.row(y + 1)
+            } else {
+                &empty
+            };
+            let row = self.row(y);
+
+            for i in 0..self.stride {
+                let mut counter = [0; 4];
+
+                for (line, centre) in [(above, true), (row, false), (below, true)] {
+                    let word = line[i];
+                    let prev = if i > 0 { line[i - 1] } else { 0 };
+                    let next = line.get(i + 1).copied().unwrap_or(0);
+
+                    // bit x of these is the cell at x - 1 and x + 1 respectively
+                    add(&mut counter, (word << 1) | (prev >> (BITS - 1)));
+                    add(&mut counter, (word >> 1) | (next << (BITS - 1)));
+
+                    if centre {
+                        add(&mut counter, word);
+                    }
+                }
+
+                let mut less = 0;
+                let mut equal = !0;
+
+                for (bit, slice) in counter.iter().enumerate().rev() {
+                    let threshold = if n >> bit & 1 == 1 { !0 } else { 0 };
+
+                    less |= equal & !slice & threshold;
+                    equal &= !(slice ^ threshold);
+                }
+
+                out.words[y * self.stride + i] = row[i] & less;
+            }
+        }
+
+        out
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        let mut out = Self::new(grid.width(), grid.height());
+
+        for (x, y) in grid.positions() {
+            if grid[(x, y)] {
+                out.set(x, y);
+            }
+        }
+
+        out
+    }
+}
+
+impl Encode for BitGrid {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.width.encode(out);
+        self.height.encode(out);
+        self.words.encode(out);
+    }
+}
+
+impl Decode<'_> for BitGrid {
+    fn decode(r: &mut Reader<'_>) -> Result<Self> {
+        let width = usize::decode(r)?;
+        let height = usize::decode(r)?;
+        let words: Vec<u64> = Vec::decode(r)?;
+        let stride = width.div_ceil(BITS);
+
+        if stride.checked_mul(height) != Some(words.len()) {
+            return Err("corrupt bit grid encoding".into());
+        }
+
+        // the bits past the width must be clear, as in every grid built by setting cells
+        if width % BITS != 0 {
+            let padding = !0 << (width % BITS);
+
+            if words
+                .chunks(stride)
+                .any(|row| row.last().is_some_and(|word| word & padding != 0))
+            {
+                return Err("corrupt bit grid encoding".into());
+            }
+        }
+
+        Ok(Self {
+            width,
+            height,
+            stride,
+            words,
+        })
+    }
+}
+
+impl fmt::Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                write!(f, "{}", if self.get(x, y) { '@' } else { '.' })?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BitGrid {}x{}\n{self}", self.width, self.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::Rng;
+
+    #[test]
+    fn set_and_clear() {
+        let mut grid = BitGrid::new(70, 2);
+        grid.set(0, 0);
+        grid.set(64, 1);
+        grid.set(69, 1);
+        grid.clear(0, 0);
+
+        assert!(!grid.get(0, 0));
+        assert!(grid.get(64, 1));
+        assert!(!grid.get(70, 1));
+        assert_eq!(grid.count_ones(), 2);
+        assert_eq!(grid.ones().collect::<Vec<_>>(), vec![(64, 1), (69, 1)]);
+    }
+
+    #[test]
+    fn decode_rejects_padding() -> Result<()> {
+        let mut grid = BitGrid::new(70, 2);
+        grid.set(69, 1);
+
+        let mut bytes = Vec::new();
+        grid.encode(&mut bytes);
+        assert!(BitGrid::decode(&mut Reader::new(&bytes))? == grid);
+
+        // the last word's lowest byte holds the row's bits 64..72, and the top two are padding
+        let last = bytes.len() - 8;
+        bytes[last] |= 0x80;
+        assert!(BitGrid::decode(&mut Reader::new(&bytes)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn neighbors_match_grid() {
+        let mut rng = Rng::new(4);
+
+        for (width, height) in [(1, 1), (10, 10), (63, 3), (64, 5), (130, 7)] {
+            let mut cells = Grid::new(width, height, false);
+
+            for (x, y) in cells.positions() {
+                cells[(x, y)] = rng.chance(1, 2);
+            }
+
+            let bits = BitGrid::from(&cells);
+
+            for n in 0..=8 {
+                let fewer = bits.set_with_fewer_neighbors(n);
+
+                for (x, y) in cells.positions() {
+                    let count = cells.neighbors8(x, y).filter(|pos| cells[*pos]).count();
+                    let expected = cells[(x, y)] && count < n as usize;
+
+                    assert_eq!(fewer.get(x, y), expected, "({x}, {y}) in {width}x{height}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! On-disk cache of answers, keyed by everything an answer depends on.
+
+use crate::Result;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub const ANSWERS: &str = ".cache/answers";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub day: u32,
+    pub part: u8,
+    /// Hash of the input bytes.
+    pub input: u64,
+    /// Hash of the solver, see [`crate::registry::Day::solver_hash`].
+    pub solver: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+pub struct Cache {
+    path: PathBuf,
+    entries: HashMap<Key, Entry>,
+    dirty: bool,
+}
+
+impl Cache {
+    /// Read the cache at `path`; a missing file is an empty cache.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let mut entries = HashMap::new();
+
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        for line in text.lines() {
+            let mut fields = line.splitn(6, ' ');
+            let mut next = || fields.next().ok_or("corrupt answer cache");
+
+            let key = Key {
+                day: next()?.parse()?,
+                part: next()?.parse()?,
+                input: u64::from_str_radix(next()?, 16)?,
+                solver: u64::from_str_radix(next()?, 16)?,
+            };
+
+            let elapsed = Duration::from_nanos(next()?.parse()?);
+            let answer = next()?.to_owned();
+
+            entries.insert(key, Entry { answer, elapsed });
+        }
+
+        Ok(Self {
+            path,
+            entries,
+            dirty: false,
+        })
+    }
+
+    pub fn get(&self, key: &Key) -> Option<&Entry> {
+        self.entries.get(key)
+    }
+
+    pub fn insert(&mut self, key: Key, entry: Entry) {
+        self.entries.insert(key, entry);
+        self.dirty = true;
+    }
+
+    /// Write the cache back if anything changed.
+    pub fn save(&self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        let mut entries: Vec<_> = self.entries.iter().collect();
+        entries.sort_by_key(|(key, _)| (key.day, key.part, key.input, key.solver));
+
+        let mut text = String::new();
+
+        for (key, entry) in entries {
+            text.push_str(&format!(
+                "{} {} {:016x} {:016x} {} {}\n",
+                key.day,
+                key.part,
+                key.input,
+                key.solver,
+                entry.elapsed.as_nanos(),
+                entry.answer
+            ));
+        }
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        std::fs::write(&self.path, text)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("aoc2025-cache-{}", std::process::id()));
+        let key = Key {
+            day: 5,
+            part: 2,
+            input: 0xdead_beef,
+            solver: 42,
+        };
+        let entry = Entry {
+            answer: "14".to_owned(),
+            elapsed: Duration::from_micros(1234),
+        };
+
+        let mut cache = Cache::load(&path)?;
+        assert_eq!(cache.get(&key), None);
+        cache.insert(key, entry.clone());
+        cache.save()?;
+
+        let cache = Cache::load(&path)?;
+        std::fs::remove_file(&path)?;
+        assert_eq!(cache.get(&key), Some(&entry));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+//! Arithmetic for the hot paths that may overflow on hostile inputs.
+//!
+//! Without the `checked` feature these are plain operators, so they wrap in release builds just
+//! like before. With it enabled, overflow becomes an error naming the day and operation, so a
+//! wrong answer caused by overflow is never submitted.
+
+use crate::Result;
+use alloc::format;
+use core::fmt::Display;
+use core::ops::{Add, Mul};
+
+pub trait Integer: Copy + Display + Add<Output = Self> + Mul<Output = Self> {
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_pow(self, exp: u32) -> Option<Self>;
+    fn pow(self, exp: u32) -> Self;
+}
+
+macro_rules! integer_impl {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn checked_pow(self, exp: u32) -> Option<Self> {
+                    <$t>::checked_pow(self, exp)
+                }
+
+                fn pow(self, exp: u32) -> Self {
+                    <$t>::pow(self, exp)
+                }
+            }
+        )*
+    };
+}
+
+integer_impl!(i32, i64, u32, u64, usize);
+
+fn overflow<T: Display>(context: &str, a: T, op: &str, b: impl Display) -> crate::Error {
+    format!("{context}: {a} {op} {b} overflows").into()
+}
+
+pub fn add<T: Integer>(context: &str, a: T, b: T) -> Result<T> {
+    if cfg!(feature = "checked") {
+        a.checked_add(b).ok_or_else(|| overflow(context, a, "+", b))
+    } else {
+        Ok(a + b)
+    }
+}
+
+pub fn mul<T: Integer>(context: &str, a: T, b: T) -> Result<T> {
+    if cfg!(feature = "checked") {
+        a.checked_mul(b).ok_or_else(|| overflow(context, a, "*", b))
+    } else {
+        Ok(a * b)
+    }
+}
+
+pub fn pow<T: Integer>(context: &str, base: T, exp: u32) -> Result<T> {
+    if cfg!(feature = "checked") {
+        base.checked_pow(exp)
+            .ok_or_else(|| overflow(context, base, "^", exp))
+    } else {
+        Ok(base.pow(exp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_range() -> Result<()> {
+        assert_eq!(add("test", 1u64, 2)?, 3);
+        assert_eq!(mul("test", 6i32, 7)?, 42);
+        assert_eq!(pow("test", 3u64, 4)?, 81);
+        Ok(())
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn overflow_is_an_error() {
+        let err = add("day02 total", u64::MAX, 1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("day02 total: {} + 1 overflows", u64::MAX)
+        );
+
+        assert!(mul("day01 turn value", i32::MAX, 10).is_err());
+        assert!(pow("day08 distance", u64::MAX, 2).is_err());
+    }
+}
@@ -1,16 +1,28 @@
+use crate::anonymize::Anonymizer;
+use crate::encode::{Decode, Encode, Reader};
+use crate::generate::{Generator, Rng};
+use crate::input;
+use crate::parse::{self, Scanner};
 use crate::prelude::*;
-use std::str::FromStr;
+use crate::simulation::{DynSimulation, Simulation};
+use core::fmt;
+use core::str::FromStr;
+use core::time::Duration;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Turn(i32);
 
 impl Turn {
-    fn left(value: i32) -> Self {
-        Self(-value.abs())
-    }
+    fn scan(s: &mut Scanner) -> parse::Result<Self> {
+        let sign = match s.peek() {
+            Some('L') => -1i32,
+            Some('R') => 1i32,
+            _ => return Err(s.error("'L' or 'R'")),
+        };
 
-    fn right(value: i32) -> Self {
-        Self(value.abs())
+        s.bump();
+
+        Ok(Self(s.unsigned::<i32>()? * sign))
     }
 }
 
@@ -18,26 +30,23 @@ impl FromStr for Turn {
     type Err = crate::Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        let mut iter = s.trim().chars();
-        let sign = match iter.next() {
-            Some('L') => -1i32,
-            Some('R') => 1i32,
-            Some(_) | None => return Err("invalid input".into()),
-        };
+        let mut s = Scanner::new(s.trim());
+        let turn = Turn::scan(&mut s)?;
+        s.finish()?;
 
-        let mut value = 0i32;
-
-        for c in iter {
-            let digit: i32 = c
-                .to_digit(10)
-                .ok_or_else(|| "invalid input".to_owned())?
-                .try_into()?;
+        Ok(turn)
+    }
+}
 
-            value *= 10;
-            value += digit;
-        }
+impl Encode for Turn {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.0.encode(out);
+    }
+}
 
-        Ok(Self(value * sign))
+impl Decode<'_> for Turn {
+    fn decode(r: &mut Reader<'_>) -> Result<Self> {
+        Ok(Self(i32::decode(r)?))
     }
 }
 
@@ -48,11 +57,8 @@ impl Dial {
     const MAX: i32 = 100; // exclusive
 
     fn add(&mut self, rhs: Turn) {
-        self.0 = (self.0 + rhs.0) % Self::MAX;
-
-        if self.0 < 0 {
-            self.0 += Self::MAX;
-        }
+        // full turns change nothing, and dropping them first keeps the sum from overflowing
+        self.0 = (self.0 + rhs.0 % Self::MAX).rem_euclid(Self::MAX);
     }
 
     fn is_zero(self) -> bool {
@@ -80,63 +86,115 @@ impl Default for Dial {
     }
 }
 
+/// Turns the dial one turn per step, counting how often it stops at and passes through zero.
+pub struct DialSimulation {
+    turns: Vec<Turn>,
+    dial: Dial,
+    stops_at_zero: u32,
+    passes_zero: u64,
+    steps: usize,
+}
+
+impl DialSimulation {
+    pub fn new(turns: Vec<Turn>) -> Self {
+        Self {
+            turns,
+            dial: Dial::default(),
+            stops_at_zero: 0,
+            passes_zero: 0,
+            steps: 0,
+        }
+    }
+}
+
+impl fmt::Display for DialSimulation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "dial at {}, stopped at zero {} times, passed zero {} times",
+            self.dial.0, self.stops_at_zero, self.passes_zero
+        )
+    }
+}
+
+impl Simulation for DialSimulation {
+    type State = Self;
+
+    fn state(&self) -> &Self {
+        self
+    }
+
+    fn step(&mut self) {
+        let Some(&turn) = self.turns.get(self.steps) else {
+            return;
+        };
+
+        self.passes_zero += self.dial.zero_crossings(turn);
+        self.dial.add(turn);
+
+        if self.dial.is_zero() {
+            self.stops_at_zero += 1;
+        }
+
+        self.steps += 1;
+    }
+
+    fn is_done(&self) -> bool {
+        self.steps == self.turns.len()
+    }
+
+    fn steps(&self) -> usize {
+        self.steps
+    }
+}
+
+pub const EXAMPLE: &str = include_str!("../examples/day01/example");
+
+const ANSWERS: &str = include_str!("../examples/day01/answers");
+
 pub struct One;
 
 impl Puzzle for One {
     type Input = Vec<Turn>;
     type Output = u32;
 
+    const LAYOUT: Layout = Layout::Lines;
+    const BUDGET: Duration = Duration::from_millis(5);
+
     fn example_input() -> Self::Input {
-        vec![
-            Turn::left(68),
-            Turn::left(30),
-            Turn::right(48),
-            Turn::left(5),
-            Turn::right(60),
-            Turn::left(55),
-            Turn::left(1),
-            Turn::left(99),
-            Turn::right(14),
-            Turn::left(82),
-        ]
+        Self::parse_input(EXAMPLE.as_bytes()).unwrap()
     }
 
     fn example_output() -> Self::Output {
-        3
+        input::example_answer(ANSWERS, 1)
     }
 
     fn input_file() -> &'static str {
         "inputs/day01/input"
     }
 
-    fn parse_input<B>(reader: B) -> Result<Self::Input>
-    where
-        B: BufRead,
-    {
-        let mut turns = vec![];
-
-        for line in reader.lines() {
-            let line = line?;
-            let turn = Turn::from_str(&line)?;
-            turns.push(turn);
-        }
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        let text = parse::text(input)?;
+        let mut s = Scanner::new(text);
+        let turns = s.lines(Turn::scan)?;
+        s.finish()?;
 
         Ok(turns)
     }
 
     fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
-        let mut dial = Dial::default();
-        let mut count = 0;
+        let mut simulation = DialSimulation::new(input);
+        simulation.run();
 
-        for turn in input {
-            dial.add(turn);
+        Ok(simulation.stops_at_zero)
+    }
 
-            if dial.is_zero() {
-                count += 1;
-            }
-        }
+    fn summary(input: &Self::Input) -> Option<String> {
+        Some(format!("{} turns", input.len()))
+    }
 
-        Ok(count)
+    fn simulation(input: Self::Input) -> Option<DynSimulation> {
+        Some(Box::new(DialSimulation::new(input)))
     }
 }
 
@@ -146,35 +204,58 @@ impl Puzzle for Two {
     type Input = Vec<Turn>;
     type Output = u64;
 
+    const LAYOUT: Layout = One::LAYOUT;
+    const BUDGET: Duration = Duration::from_millis(5);
+
     fn example_input() -> Self::Input {
         One::example_input()
     }
 
     fn example_output() -> Self::Output {
-        6
+        input::example_answer(ANSWERS, 2)
     }
 
     fn input_file() -> &'static str {
         One::input_file()
     }
 
-    fn parse_input<B>(reader: B) -> Result<Self::Input>
-    where
-        B: BufRead,
-    {
-        One::parse_input(reader)
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        One::parse_input(input)
     }
 
     fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
-        let mut dial = Dial::default();
-        let mut count: u64 = 0;
+        let mut simulation = DialSimulation::new(input);
+        simulation.run();
 
-        for turn in input {
-            count += dial.zero_crossings(turn);
-            dial.add(turn);
-        }
+        Ok(simulation.passes_zero)
+    }
+}
 
-        Ok(count)
+impl Anonymizer for One {
+    /// Shuffles the turns and moves every distance by up to a tenth.
+    fn anonymize(text: &str, rng: &mut Rng) -> Result<String> {
+        let mut turns = Self::parse_input(text.as_bytes())?;
+        rng.shuffle(&mut turns);
+
+        Ok(turns
+            .iter()
+            .map(|turn| {
+                let direction = if turn.0 < 0 { 'L' } else { 'R' };
+                let distance = u64::from(turn.0.unsigned_abs());
+                format!("{direction}{}\n", rng.jitter(distance, distance / 10))
+            })
+            .collect())
+    }
+}
+
+impl Generator for One {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                let direction = if rng.chance(1, 2) { 'L' } else { 'R' };
+                format!("{direction}{}\n", rng.range(1..=999))
+            })
+            .collect()
     }
 }
 
@@ -193,4 +274,14 @@ mod tests {
         let mut two = Two;
         two.test_example()
     }
+
+    #[test]
+    fn huge_turns() {
+        let mut dial = Dial::default();
+        dial.add(Turn(i32::MAX));
+        assert_eq!(dial, Dial(97));
+
+        dial.add(Turn(-i32::MAX));
+        assert_eq!(dial, Dial(50));
+    }
 }
@@ -1,69 +1,201 @@
+use crate::anonymize::Anonymizer;
+use crate::checked;
+use crate::generate::{Generator, Rng};
+use crate::input;
+use crate::parse::{self, Scanner};
 use crate::prelude::*;
-use std::ops::RangeInclusive;
+use core::ops::RangeInclusive;
+use core::time::Duration;
 
-pub struct One;
+pub const EXAMPLE: &str = include_str!("../examples/day02/example");
 
-impl One {
-    fn check_number(n: u64) -> bool {
-        let s = n.to_string();
-        let bytes = s.as_bytes();
+const ANSWERS: &str = include_str!("../examples/day02/answers");
 
-        if !bytes.len().is_multiple_of(2) {
-            return false;
-        }
+/// Number of decimal digits in `u64::MAX`.
+const MAX_DIGITS: u32 = 20;
 
-        let half = bytes.len() / 2;
+/// The `len`-digit numbers made of a `chunk_len`-digit block repeated are exactly the block
+/// multiplied by `10^0 + 10^chunk_len + 10^(2 * chunk_len) + ...`, e.g. `123123 = 123 * 1001`.
+fn multiplier(len: u32, chunk_len: u32) -> u128 {
+    (10u128.pow(len) - 1) / (10u128.pow(chunk_len) - 1)
+}
 
-        bytes[..half] == bytes[half..]
-    }
+/// Returns the [`multiplier`] and the blocks whose repetitions fall into `range`.
+fn repetitions(
+    range: &RangeInclusive<u64>,
+    len: u32,
+    chunk_len: u32,
+) -> (u128, RangeInclusive<u128>) {
+    let multiplier = multiplier(len, chunk_len);
+
+    let first = u128::from(*range.start())
+        .div_ceil(multiplier)
+        .max(10u128.pow(chunk_len - 1));
+    let last = (u128::from(*range.end()) / multiplier).min(10u128.pow(chunk_len) - 1);
+
+    (multiplier, first..=last)
+}
+
+/// Whether `block`, `chunk_len` digits long, is itself a shorter block repeated. Its
+/// repetitions are then also repetitions of that shorter block.
+fn is_repetition(block: u128, chunk_len: u32) -> bool {
+    (1..chunk_len)
+        .filter(|len| chunk_len.is_multiple_of(*len))
+        .any(|len| block.is_multiple_of(multiplier(chunk_len, len)))
 }
 
+fn range(s: &mut Scanner) -> parse::Result<RangeInclusive<u64>> {
+    let [start, end] = s.tuple('-', Scanner::unsigned)?;
+
+    Ok(start..=end)
+}
+
+pub struct One;
+
 impl Puzzle for One {
     type Input = Vec<RangeInclusive<u64>>;
     type Output = u64;
 
-    fn example_input() -> Self::Input {
-        let s = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,
-1698522-1698528,446443-446449,38593856-38593862,565653-565659,
-824824821-824824827,2121212118-2121212124"
-            .to_owned();
-
-        let reader = BufReader::new(s.as_bytes());
+    const LAYOUT: Layout = Layout::Lines;
+    const BUDGET: Duration = Duration::from_millis(5);
 
-        Self::parse_input(reader).unwrap()
+    fn example_input() -> Self::Input {
+        Self::parse_input(EXAMPLE.as_bytes()).unwrap()
     }
 
     fn example_output() -> Self::Output {
-        1227775554
+        input::example_answer(ANSWERS, 1)
     }
 
     fn input_file() -> &'static str {
         "inputs/day02/input"
     }
 
-    fn parse_input<B>(reader: B) -> Result<Self::Input>
-    where
-        B: BufRead,
-    {
-        let mut ranges = Vec::new();
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        let text = parse::text(input)?;
+        let mut s = Scanner::new(text);
+        let lines = s.lines(|s| s.separated(',', range))?;
+        s.finish()?;
 
-        for line in reader.lines() {
-            let line = line?;
+        Ok(lines.into_iter().flatten().collect())
+    }
 
-            for range in line.split(',') {
-                if range.is_empty() || range.chars().all(char::is_whitespace) {
+    fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
+        let mut total = 0;
+
+        for range in input {
+            for len in (2..=MAX_DIGITS).step_by(2) {
+                let (multiplier, blocks) = repetitions(&range, len, len / 2);
+
+                if blocks.is_empty() {
                     continue;
                 }
 
-                let mut split = range.split('-');
-                let start = split.next().ok_or("invalid input")?.parse::<u64>()?;
-                let end = split.next().ok_or("invalid input")?.parse::<u64>()?;
+                let count = blocks.end() - blocks.start() + 1;
+                let sum = multiplier * (blocks.start() + blocks.end()) * count / 2;
+
+                total = checked::add("day02 sum of invalid ids", total, u64::try_from(sum)?)?;
+            }
+        }
+
+        Ok(total)
+    }
+
+    fn summary(input: &Self::Input) -> Option<String> {
+        let span: u128 = input
+            .iter()
+            .map(|range| (u128::from(*range.end()) + 1).saturating_sub(u128::from(*range.start())))
+            .sum();
+
+        Some(format!("{} ranges spanning {span} ids", input.len()))
+    }
+}
+
+pub struct Two;
+
+impl Puzzle for Two {
+    type Input = Vec<RangeInclusive<u64>>;
+    type Output = u64;
+
+    const LAYOUT: Layout = One::LAYOUT;
+    const BUDGET: Duration = Duration::from_millis(5);
+
+    fn example_input() -> Self::Input {
+        One::example_input()
+    }
+
+    fn example_output() -> Self::Output {
+        input::example_answer(ANSWERS, 2)
+    }
+
+    fn input_file() -> &'static str {
+        One::input_file()
+    }
+
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        One::parse_input(input)
+    }
+
+    fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
+        let mut total = 0;
 
-                ranges.push(RangeInclusive::new(start, end));
+        for range in input {
+            for len in 2..=MAX_DIGITS {
+                for chunk_len in (1..=len / 2).filter(|chunk_len| len.is_multiple_of(*chunk_len)) {
+                    let (multiplier, blocks) = repetitions(&range, len, chunk_len);
+
+                    // a number may repeat several block lengths, e.g. 222222, so count it only
+                    // with its shortest block
+                    for block in blocks.filter(|block| !is_repetition(*block, chunk_len)) {
+                        let number = u64::try_from(block * multiplier)?;
+                        total = checked::add("day02 sum of invalid ids", total, number)?;
+                    }
+                }
             }
         }
 
-        Ok(ranges)
+        Ok(total)
+    }
+}
+
+/// Reference implementation of [`One`], checking every number in every range.
+pub struct NaiveOne;
+
+impl NaiveOne {
+    fn check_number(n: u64) -> bool {
+        let s = n.to_string();
+        let bytes = s.as_bytes();
+
+        if !bytes.len().is_multiple_of(2) {
+            return false;
+        }
+
+        let half = bytes.len() / 2;
+
+        bytes[..half] == bytes[half..]
+    }
+}
+
+impl Puzzle for NaiveOne {
+    type Input = <One as Puzzle>::Input;
+    type Output = <One as Puzzle>::Output;
+
+    const LAYOUT: Layout = One::LAYOUT;
+
+    fn example_input() -> Self::Input {
+        One::example_input()
+    }
+
+    fn example_output() -> Self::Output {
+        One::example_output()
+    }
+
+    fn input_file() -> &'static str {
+        One::input_file()
+    }
+
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        One::parse_input(input)
     }
 
     fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
@@ -72,7 +204,7 @@ impl Puzzle for One {
         for range in input {
             for number in range {
                 if Self::check_number(number) {
-                    total += number
+                    total = checked::add("day02 sum of invalid ids", total, number)?;
                 }
             }
         }
@@ -81,9 +213,10 @@ impl Puzzle for One {
     }
 }
 
-pub struct Two;
+/// Reference implementation of [`Two`], checking every number in every range.
+pub struct NaiveTwo;
 
-impl Two {
+impl NaiveTwo {
     fn check_number(n: u64) -> bool {
         let s = n.to_string();
         let bytes = s.as_bytes();
@@ -111,27 +244,26 @@ impl Two {
     }
 }
 
-impl Puzzle for Two {
-    type Input = Vec<RangeInclusive<u64>>;
-    type Output = u64;
+impl Puzzle for NaiveTwo {
+    type Input = <Two as Puzzle>::Input;
+    type Output = <Two as Puzzle>::Output;
+
+    const LAYOUT: Layout = Two::LAYOUT;
 
     fn example_input() -> Self::Input {
-        One::example_input()
+        Two::example_input()
     }
 
     fn example_output() -> Self::Output {
-        4174379265
+        Two::example_output()
     }
 
     fn input_file() -> &'static str {
-        One::input_file()
+        Two::input_file()
     }
 
-    fn parse_input<B>(reader: B) -> Result<Self::Input>
-    where
-        B: BufRead,
-    {
-        One::parse_input(reader)
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        Two::parse_input(input)
     }
 
     fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
@@ -140,7 +272,7 @@ impl Puzzle for Two {
         for range in input {
             for number in range {
                 if Self::check_number(number) {
-                    total += number
+                    total = checked::add("day02 sum of invalid ids", total, number)?;
                 }
             }
         }
@@ -149,6 +281,52 @@ impl Puzzle for Two {
     }
 }
 
+impl Anonymizer for One {
+    /// Shuffles the ranges and moves both ends of each by up to a tenth of its length, keeping
+    /// as many ranges on each line as before.
+    fn anonymize(text: &str, rng: &mut Rng) -> Result<String> {
+        let mut ranges = Self::parse_input(text.as_bytes())?;
+        rng.shuffle(&mut ranges);
+
+        let mut ranges = ranges.into_iter().map(|range| {
+            let spread = (range.end() - range.start()) / 10;
+            let start = rng.jitter(*range.start(), spread).max(1);
+            let end = rng.jitter(*range.end(), spread).max(start);
+            format!("{start}-{end}")
+        });
+
+        let mut out = String::new();
+
+        for line in text.lines() {
+            let n = line.split(',').filter(|range| !range.is_empty()).count();
+            out.push_str(&ranges.by_ref().take(n).collect::<Vec<_>>().join(","));
+
+            if line.ends_with(',') {
+                out.push(',');
+            }
+
+            out.push('\n');
+        }
+
+        Ok(out)
+    }
+}
+
+impl Generator for One {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let ranges: Vec<String> = (0..size)
+            .map(|_| {
+                let digits = rng.range(1..=10) as u32;
+                let start = rng.range(10u64.pow(digits - 1)..=10u64.pow(digits) - 1);
+                let end = start + rng.below(10_000);
+                format!("{start}-{end}")
+            })
+            .collect();
+
+        format!("{}\n", ranges.join(","))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -164,4 +342,13 @@ mod tests {
         let mut two = Two;
         two.test_example()
     }
+
+    #[test]
+    fn naive() -> Result<()> {
+        let mut one = NaiveOne;
+        one.test_example()?;
+
+        let mut two = NaiveTwo;
+        two.test_example()
+    }
 }
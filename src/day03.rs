@@ -1,8 +1,17 @@
+use crate::anonymize::Anonymizer;
+use crate::checked;
+use crate::generate::{Generator, Rng};
+use crate::input;
+use crate::parse::{self, Scanner};
 use crate::prelude::*;
+use crate::puzzle::ZeroCopy;
+use core::time::Duration;
 
+/// Joltages of a bank of batteries, given as the ASCII digits of the input.
 trait Helper {
     fn idx_of_max(&self) -> usize;
-    fn max_joltage(&self, digits: usize) -> u64;
+    fn max_joltage(&self, digits: usize) -> Result<u64>;
+    fn max_joltage_with(&self, digits: usize, on_pick: impl FnMut(usize)) -> Result<u64>;
 }
 
 impl<T> Helper for T
@@ -23,7 +32,12 @@ where
         max_idx
     }
 
-    fn max_joltage(&self, digits: usize) -> u64 {
+    fn max_joltage(&self, digits: usize) -> Result<u64> {
+        self.max_joltage_with(digits, |_| ())
+    }
+
+    /// Like [`Helper::max_joltage`], calling `on_pick` with the index of every chosen battery.
+    fn max_joltage_with(&self, digits: usize, mut on_pick: impl FnMut(usize)) -> Result<u64> {
         let mut out = 0;
 
         let mut prev_max_idx = 0;
@@ -35,70 +49,106 @@ where
             let slice = &self.as_ref()[from..=to];
             let next_idx = from + slice.idx_of_max();
 
-            out *= 10;
-            out += self.as_ref()[next_idx] as u64;
+            out = checked::mul("day03 joltage", out, 10)?;
+            out = checked::add(
+                "day03 joltage",
+                out,
+                (self.as_ref()[next_idx] - b'0') as u64,
+            )?;
 
+            on_pick(next_idx);
             prev_max_idx = next_idx;
         }
 
-        out
+        Ok(out)
     }
 }
 
+/// Every bank's digits, as slices of the input.
+fn banks(input: &[u8]) -> Result<Vec<&[u8]>> {
+    let text = parse::text(input)?;
+    let mut s = Scanner::new(text);
+    let banks = s.lines(|s| match s.take_while(|c| c.is_ascii_digit()) {
+        "" => Err(s.error("digit")),
+        bank => Ok(bank.as_bytes()),
+    })?;
+    s.finish()?;
+
+    Ok(banks)
+}
+
+/// The sum of every bank's largest joltage from `digits` batteries.
+fn total_joltage(banks: &[impl AsRef<[u8]>], digits: usize) -> Result<u64> {
+    let mut total = 0;
+
+    for bank in banks {
+        total = checked::add("day03 total joltage", total, bank.max_joltage(digits)?)?;
+    }
+
+    Ok(total)
+}
+
+/// The batteries chosen in every bank and the joltage they make.
+fn explain(banks: &[impl AsRef<[u8]>], digits: usize) -> Result<String> {
+    let mut out = String::new();
+
+    for (i, bank) in banks.iter().enumerate() {
+        let mut picked = Vec::with_capacity(digits);
+        let joltage = bank.max_joltage_with(digits, |idx| picked.push(idx))?;
+
+        out.push_str(&format!("bank {i}: batteries {picked:?} make {joltage}\n"));
+    }
+
+    Ok(out)
+}
+
+pub const EXAMPLE: &str = include_str!("../examples/day03/example");
+
+const ANSWERS: &str = include_str!("../examples/day03/answers");
+
 pub struct One;
 
 impl Puzzle for One {
+    /// Every bank's digits, copied out of the input.
     type Input = Vec<Vec<u8>>;
     type Output = u64;
 
-    fn example_input() -> Self::Input {
-        let s = "987654321111111
-811111111111119
-234234234234278
-818181911112111"
-            .to_owned();
-
-        let reader = BufReader::new(s.as_bytes());
+    const LAYOUT: Layout = Layout::Lines;
+    const BUDGET: Duration = Duration::from_millis(5);
 
-        Self::parse_input(reader).unwrap()
+    fn example_input() -> Self::Input {
+        Self::parse_input(EXAMPLE.as_bytes()).unwrap()
     }
 
     fn example_output() -> Self::Output {
-        357
+        input::example_answer(ANSWERS, 1)
     }
 
     fn input_file() -> &'static str {
         "inputs/day03/input"
     }
 
-    fn parse_input<B>(reader: B) -> Result<Self::Input>
-    where
-        B: BufRead,
-    {
-        let mut banks = Vec::new();
-
-        for line in reader.lines() {
-            let mut batteries = Vec::new();
-
-            for char in line?.trim().chars() {
-                let battery = char.to_digit(10).ok_or("invalid input")? as u8;
-                batteries.push(battery)
-            }
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        Ok(banks(input)?.into_iter().map(<[u8]>::to_vec).collect())
+    }
 
-            banks.push(batteries);
-        }
+    fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
+        total_joltage(&input, 2)
+    }
 
-        Ok(banks)
+    fn explain(&mut self, input: Self::Input) -> Result<Option<String>> {
+        explain(&input, 2).map(Some)
     }
 
-    fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
-        let mut total = 0;
+    fn summary(input: &Self::Input) -> Option<String> {
+        let min = input.iter().map(Vec::len).min().unwrap_or(0);
+        let max = input.iter().map(Vec::len).max().unwrap_or(0);
 
-        for bank in input {
-            total += bank.max_joltage(2);
+        if min == max {
+            Some(format!("{} banks of {min} batteries", input.len()))
+        } else {
+            Some(format!("{} banks of {min} to {max} batteries", input.len()))
         }
-
-        Ok(total)
     }
 }
 
@@ -108,33 +158,90 @@ impl Puzzle for Two {
     type Input = <One as Puzzle>::Input;
     type Output = <One as Puzzle>::Output;
 
+    const LAYOUT: Layout = One::LAYOUT;
+    const BUDGET: Duration = Duration::from_millis(5);
+
     fn example_input() -> Self::Input {
         One::example_input()
     }
 
     fn example_output() -> Self::Output {
-        3121910778619
+        input::example_answer(ANSWERS, 2)
     }
 
     fn input_file() -> &'static str {
         One::input_file()
     }
 
-    fn parse_input<B>(reader: B) -> Result<Self::Input>
-    where
-        B: BufRead,
-    {
-        One::parse_input(reader)
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        One::parse_input(input)
     }
 
     fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
-        let mut total = 0;
+        total_joltage(&input, 12)
+    }
 
-        for bank in input {
-            total += bank.max_joltage(12);
+    fn explain(&mut self, input: Self::Input) -> Result<Option<String>> {
+        explain(&input, 12).map(Some)
+    }
+}
+
+impl ZeroCopy for One {
+    type Borrowed<'a> = Vec<&'a [u8]>;
+
+    fn parse_borrowed(input: &[u8]) -> Result<Self::Borrowed<'_>> {
+        banks(input)
+    }
+
+    fn solve_borrowed(&mut self, input: Self::Borrowed<'_>) -> Result<Self::Output> {
+        total_joltage(&input, 2)
+    }
+}
+
+impl ZeroCopy for Two {
+    type Borrowed<'a> = <One as ZeroCopy>::Borrowed<'a>;
+
+    fn parse_borrowed(input: &[u8]) -> Result<Self::Borrowed<'_>> {
+        One::parse_borrowed(input)
+    }
+
+    fn solve_borrowed(&mut self, input: Self::Borrowed<'_>) -> Result<Self::Output> {
+        total_joltage(&input, 12)
+    }
+}
+
+impl Anonymizer for One {
+    /// Shuffles the banks and the batteries within each.
+    fn anonymize(text: &str, rng: &mut Rng) -> Result<String> {
+        let mut banks = Self::parse_input(text.as_bytes())?;
+        rng.shuffle(&mut banks);
+
+        let mut out = String::new();
+
+        for bank in &mut banks {
+            rng.shuffle(bank);
+            out.extend(bank.iter().map(|digit| char::from(*digit)));
+            out.push('\n');
         }
 
-        Ok(total)
+        Ok(out)
+    }
+}
+
+impl Generator for One {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut out = String::new();
+
+        for _ in 0..size {
+            // part two picks 12 batteries, so no bank may be shorter than that
+            for _ in 0..rng.range(12..=100) {
+                out.push(char::from(b'1' + rng.below(9) as u8));
+            }
+
+            out.push('\n');
+        }
+
+        out
     }
 }
 
@@ -153,4 +260,24 @@ mod tests {
         let mut two = Two;
         two.test_example()
     }
+
+    #[test]
+    fn borrowed() -> Result<()> {
+        let banks = One::parse_borrowed(EXAMPLE.as_bytes())?;
+
+        assert_eq!(One.solve_borrowed(banks.clone())?, One::example_output());
+        assert_eq!(Two.solve_borrowed(banks)?, Two::example_output());
+        Ok(())
+    }
+
+    #[test]
+    fn explain() -> Result<()> {
+        let explanation = One.explain(One::example_input())?.unwrap();
+
+        assert_eq!(
+            explanation.lines().next(),
+            Some("bank 0: batteries [0, 1] make 98")
+        );
+        Ok(())
+    }
 }
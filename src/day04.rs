@@ -1,171 +1,256 @@
+use crate::anonymize::Anonymizer;
+use crate::bitgrid::BitGrid;
+use crate::generate::{Generator, Rng};
+use crate::grid::Grid;
+use crate::input;
 use crate::prelude::*;
+#[cfg(feature = "std")]
+use crate::render::{Color, Scene};
+use crate::simulation::{DynSimulation, Simulation};
+use core::fmt;
+use core::time::Duration;
+
+/// Rolls with fewer than four neighbouring rolls.
+fn accessible_rolls(grid: &BitGrid) -> BitGrid {
+    grid.set_with_fewer_neighbors(4)
+}
+
+/// Removes every accessible roll in one step. Removing a roll only makes its neighbours more
+/// accessible, so removing whole rounds at once ends with the same rolls left as removing them
+/// one by one.
+pub struct Removal {
+    grid: BitGrid,
+    last_round: BitGrid,
+    next_round: BitGrid,
+    removed: usize,
+    steps: usize,
+}
+
+impl Removal {
+    pub fn new(grid: BitGrid) -> Self {
+        Self {
+            last_round: BitGrid::new(grid.width(), grid.height()),
+            next_round: accessible_rolls(&grid),
+            grid,
+            removed: 0,
+            steps: 0,
+        }
+    }
+}
+
+/// The remaining rolls as `@`, with those removed in the last step as `x`.
+impl fmt::Display for Removal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.grid.height() {
+            for x in 0..self.grid.width() {
+                let c = match (self.grid.get(x, y), self.last_round.get(x, y)) {
+                    (true, _) => '@',
+                    (false, true) => 'x',
+                    (false, false) => '.',
+                };
+
+                write!(f, "{c}")?;
+            }
+
+            writeln!(f)?;
+        }
+
+        write!(f, "{} rolls removed", self.removed)
+    }
+}
+
+impl Simulation for Removal {
+    type State = Self;
 
-fn surrounding_indices(x: usize, y: usize) -> [(usize, usize); 8] {
-    [
-        (x.overflowing_sub(1).0, y),                      // left
-        (x + 1, y),                                       // right
-        (x, y.overflowing_sub(1).0),                      // above
-        (x, y + 1),                                       // below
-        (x.overflowing_sub(1).0, y.overflowing_sub(1).0), // top left
-        (x + 1, y.overflowing_sub(1).0),                  // top right
-        (x.overflowing_sub(1).0, y + 1),                  // bottom left
-        (x + 1, y + 1),                                   // bottom right
-    ]
+    fn state(&self) -> &Self {
+        self
+    }
+
+    fn step(&mut self) {
+        if self.is_done() {
+            return;
+        }
+
+        self.grid.clear_all(&self.next_round);
+        self.removed += self.next_round.count_ones();
+
+        let next_round = accessible_rolls(&self.grid);
+        self.last_round = core::mem::replace(&mut self.next_round, next_round);
+        self.steps += 1;
+    }
+
+    fn is_done(&self) -> bool {
+        self.next_round.is_empty()
+    }
+
+    fn steps(&self) -> usize {
+        self.steps
+    }
 }
 
+pub const EXAMPLE: &str = include_str!("../examples/day04/example");
+
+const ANSWERS: &str = include_str!("../examples/day04/answers");
+
 pub struct One;
 
 impl Puzzle for One {
-    type Input = Vec<Vec<bool>>;
+    type Input = BitGrid;
     type Output = u32;
 
-    fn example_input() -> Self::Input {
-        let s = "..@@.@@@@.
-@@@.@.@.@@
-@@@@@.@.@@
-@.@@@@..@.
-@@.@@@@.@@
-.@@@@@@@.@
-.@.@.@.@@@
-@.@@@.@@@@
-.@@@@@@@@.
-@.@.@@@.@."
-            .to_owned();
-
-        let reader = BufReader::new(s.as_bytes());
+    const LAYOUT: Layout = Layout::Lines;
+    const BUDGET: Duration = Duration::from_millis(5);
 
-        Self::parse_input(reader).unwrap()
+    fn example_input() -> Self::Input {
+        Self::parse_input(EXAMPLE.as_bytes()).unwrap()
     }
 
     fn example_output() -> Self::Output {
-        13
+        input::example_answer(ANSWERS, 1)
     }
 
     fn input_file() -> &'static str {
         "inputs/day04/input"
     }
 
-    fn parse_input<B>(reader: B) -> Result<Self::Input>
-    where
-        B: BufRead,
-    {
-        let mut grid = Vec::new();
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        let grid = Grid::parse(input, |c| match c {
+            '.' => Ok(false),
+            '@' => Ok(true),
+            _ => Err("invalid input".into()),
+        })?;
 
-        for line in reader.lines() {
-            let mut row = Vec::new();
+        Ok(BitGrid::from(&grid))
+    }
 
-            for c in line?.trim().chars() {
-                match c {
-                    '.' => row.push(false),
-                    '@' => row.push(true),
-                    _ => return Err("invalid input".into()),
-                }
-            }
+    fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
+        Ok(accessible_rolls(&input).count_ones().try_into()?)
+    }
 
-            grid.push(row);
-        }
+    fn summary(input: &Self::Input) -> Option<String> {
+        let (width, height) = (input.width(), input.height());
 
-        Ok(grid)
+        let filled = input.count_ones();
+        let ratio = filled as f64 / (width * height).max(1) as f64 * 100.0;
+
+        Some(format!("{width}x{height} grid, {ratio:.1}% filled"))
     }
 
-    fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
-        let length = input.len();
-        let width = input[0].len();
+    /// Rolls shaded from red to yellow by the round they are removed in; grey ones stay.
+    #[cfg(feature = "std")]
+    fn render(input: &Self::Input) -> Option<Scene> {
+        let mut scene = Scene::new(input.width() as u64, input.height() as u64);
 
-        let mut reachable_rolls = 0;
+        for (x, y) in input.ones() {
+            scene.cell(x as u64, y as u64, '@', Color::GREY);
+        }
 
-        for (y, row) in input.iter().enumerate() {
-            for (x, place) in row.iter().enumerate() {
-                if !place {
-                    continue;
-                }
+        let mut removal = Removal::new(input.clone());
+        let mut rounds = Vec::new();
 
-                let mut adjacent_rolls = 0;
+        while !removal.is_done() {
+            removal.step();
+            rounds.push(removal.last_round.clone());
+        }
 
-                for (other_x, other_y) in surrounding_indices(x, y) {
-                    if other_x < width && other_y < length && input[other_y][other_x] {
-                        adjacent_rolls += 1;
-                        if adjacent_rolls >= 4 {
-                            break;
-                        }
-                    }
-                }
+        for (i, round) in rounds.iter().enumerate() {
+            let color = Color::RED.mix(Color::YELLOW, i as f64 / rounds.len() as f64);
 
-                if adjacent_rolls < 4 {
-                    reachable_rolls += 1;
-                }
+            for (x, y) in round.ones() {
+                scene.cell(x as u64, y as u64, 'x', color);
             }
         }
 
-        Ok(reachable_rolls)
+        Some(scene)
     }
 }
 
 pub struct Two;
 
 impl Puzzle for Two {
-    type Input = Vec<Vec<bool>>;
+    type Input = BitGrid;
     type Output = usize;
 
+    const LAYOUT: Layout = One::LAYOUT;
+    const BUDGET: Duration = Duration::from_millis(5);
+
     fn example_input() -> Self::Input {
         One::example_input()
     }
 
     fn example_output() -> Self::Output {
-        43
+        input::example_answer(ANSWERS, 2)
     }
 
     fn input_file() -> &'static str {
         One::input_file()
     }
 
-    fn parse_input<B>(reader: B) -> Result<Self::Input>
-    where
-        B: BufRead,
-    {
-        One::parse_input(reader)
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        One::parse_input(input)
+    }
+
+    fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
+        let mut removal = Removal::new(input);
+        removal.run();
+
+        Ok(removal.removed)
     }
 
-    fn solve(&mut self, mut input: Self::Input) -> Result<Self::Output> {
-        let length = input.len();
-        let width = input[0].len();
+    fn simulation(input: Self::Input) -> Option<DynSimulation> {
+        Some(Box::new(Removal::new(input)))
+    }
+}
 
-        let mut removed_rolls = 0;
+impl Anonymizer for One {
+    /// Mirrors the grid either way at random and toggles one cell in fifty.
+    fn anonymize(text: &str, rng: &mut Rng) -> Result<String> {
+        Self::parse_input(text.as_bytes())?;
 
-        loop {
-            let mut removed_this_round = 0;
+        let mut rows: Vec<Vec<char>> = text.lines().map(|row| row.chars().collect()).collect();
+        let mirror = rng.chance(1, 2);
 
-            for y in 0..length {
-                for x in 0..width {
-                    if !input[y][x] {
-                        continue;
-                    }
+        if rng.chance(1, 2) {
+            rows.reverse();
+        }
 
-                    let mut adjacent_rolls = 0;
+        let mut out = String::new();
 
-                    for (other_x, other_y) in surrounding_indices(x, y) {
-                        if other_x < width && other_y < length && input[other_y][other_x] {
-                            adjacent_rolls += 1;
-                            if adjacent_rolls >= 4 {
-                                break;
-                            }
-                        }
-                    }
+        for row in &mut rows {
+            if mirror {
+                row.reverse();
+            }
 
-                    if adjacent_rolls < 4 {
-                        input[y][x] = false;
-                        removed_rolls += 1;
-                        removed_this_round += 1;
-                    }
+            for c in row {
+                if rng.chance(1, 50) {
+                    *c = if *c == '@' { '.' } else { '@' };
                 }
+
+                out.push(*c);
             }
 
-            if removed_this_round == 0 {
-                break;
+            out.push('\n');
+        }
+
+        Ok(out)
+    }
+}
+
+impl Generator for One {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(1);
+        let density = rng.range(30..=80);
+        let mut out = String::new();
+
+        for _ in 0..size {
+            for _ in 0..size {
+                out.push(if rng.chance(density, 100) { '@' } else { '.' });
             }
+
+            out.push('\n');
         }
 
-        Ok(removed_rolls)
+        out
     }
 }
 
@@ -1,92 +1,161 @@
+use crate::anonymize::Anonymizer;
+use crate::encode::{Decode, Encode, Reader};
+use crate::generate::{Generator, Rng};
+use crate::input;
+use crate::intervals::IntervalSet;
+use crate::parse::{self, Scanner};
 use crate::prelude::*;
-use std::collections::HashSet;
-use std::ops::RangeInclusive;
+use core::ops::RangeInclusive;
+use core::time::Duration;
 
 pub struct Database {
-    fresh_ingredients: HashSet<RangeInclusive<u64>>,
-    available_ingredients: HashSet<u64>,
+    fresh_ingredients: Vec<RangeInclusive<u64>>,
+    available_ingredients: Vec<u64>,
 }
 
+impl Encode for Database {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.fresh_ingredients.encode(out);
+        self.available_ingredients.encode(out);
+    }
+}
+
+impl Decode<'_> for Database {
+    fn decode(r: &mut Reader<'_>) -> Result<Self> {
+        Ok(Self {
+            fresh_ingredients: Vec::decode(r)?,
+            available_ingredients: Vec::decode(r)?,
+        })
+    }
+}
+
+pub const EXAMPLE: &str = include_str!("../examples/day05/example");
+
+const ANSWERS: &str = include_str!("../examples/day05/answers");
+
 pub struct One;
 
 impl Puzzle for One {
     type Input = Database;
     type Output = u64;
 
-    fn example_input() -> Self::Input {
-        let s = "3-5
-10-14
-16-20
-12-18
-
-1
-5
-8
-11
-17
-32"
-        .to_owned();
+    const LAYOUT: Layout = Layout::Lines;
+    const BUDGET: Duration = Duration::from_millis(5);
 
-        let reader = BufReader::new(s.as_bytes());
-        Self::parse_input(reader).unwrap()
+    fn example_input() -> Self::Input {
+        Self::parse_input(EXAMPLE.as_bytes()).unwrap()
     }
 
     fn example_output() -> Self::Output {
-        3
+        input::example_answer(ANSWERS, 1)
     }
 
     fn input_file() -> &'static str {
         "inputs/day05/input"
     }
 
-    fn parse_input<B>(reader: B) -> Result<Self::Input>
-    where
-        B: BufRead,
-    {
-        let mut fresh_ingredients = HashSet::new();
-        let mut available_ingredients = HashSet::new();
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        let text = parse::text(input)?;
+        let mut s = Scanner::new(text);
+        let fresh_ingredients = s.lines(|s| {
+            let [start, end] = s.tuple('-', Scanner::unsigned)?;
+            Ok(start..=end)
+        })?;
+        s.end_of_line()?;
+        let available_ingredients = s.lines(Scanner::unsigned)?;
+        s.finish()?;
 
-        let mut flag = false;
+        Ok(Database {
+            fresh_ingredients,
+            available_ingredients,
+        })
+    }
+
+    fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
+        let fresh: IntervalSet<u64> = input.fresh_ingredients.into_iter().collect();
 
-        for line in reader.lines() {
-            let tmp = line?;
-            let trimmed = tmp.trim();
+        Ok(input
+            .available_ingredients
+            .into_iter()
+            .filter(|ingredient| fresh.contains(*ingredient))
+            .count() as u64)
+    }
 
-            if trimmed.is_empty() {
-                flag = true;
-                continue;
-            }
+    fn explain(&mut self, input: Self::Input) -> Result<Option<String>> {
+        let fresh: IntervalSet<u64> = input.fresh_ingredients.into_iter().collect();
+        let mut available = input.available_ingredients;
+        available.sort_unstable();
 
-            if flag {
-                available_ingredients.insert(trimmed.parse()?);
-            } else {
-                let mut it = trimmed.split('-');
-                let start = it.next().ok_or("invalid input")?.parse()?;
-                let end = it.next().ok_or("invalid input")?.parse()?;
+        let mut out = format!("fresh ranges merged into {fresh:?}\n");
 
-                fresh_ingredients.insert(RangeInclusive::new(start, end));
-            }
+        for ingredient in available {
+            let verdict = if fresh.contains(ingredient) {
+                "fresh"
+            } else {
+                "spoiled"
+            };
+            out.push_str(&format!("ingredient {ingredient} is {verdict}\n"));
         }
 
-        Ok(Database {
-            fresh_ingredients,
-            available_ingredients,
-        })
+        Ok(Some(out))
+    }
+
+    fn summary(input: &Self::Input) -> Option<String> {
+        Some(format!(
+            "{} fresh ranges, {} available ingredients",
+            input.fresh_ingredients.len(),
+            input.available_ingredients.len()
+        ))
+    }
+}
+
+pub struct Two;
+
+impl Puzzle for Two {
+    type Input = IntervalSet<u64>;
+    type Output = u64;
+
+    const LAYOUT: Layout = One::LAYOUT;
+    const BUDGET: Duration = Duration::from_millis(5);
+
+    fn example_input() -> Self::Input {
+        One::example_input().fresh_ingredients.into_iter().collect()
+    }
+
+    fn example_output() -> Self::Output {
+        input::example_answer(ANSWERS, 2)
+    }
+
+    fn input_file() -> &'static str {
+        One::input_file()
+    }
+
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        Ok(One::parse_input(input)?
+            .fresh_ingredients
+            .into_iter()
+            .collect())
     }
 
     fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
-        let mut count = 0;
-
-        for ingredient in input.available_ingredients {
-            for range in input.fresh_ingredients.iter() {
-                if range.contains(&ingredient) {
-                    count += 1;
-                    break;
-                }
-            }
+        Ok(input.cardinality().try_into()?)
+    }
+
+    fn explain(&mut self, input: Self::Input) -> Result<Option<String>> {
+        let mut out = String::new();
+
+        for range in input.iter() {
+            let ids = u128::from(*range.end() - *range.start()) + 1;
+            out.push_str(&format!("{}-{}: {ids} ids\n", range.start(), range.end()));
         }
 
-        Ok(count)
+        out.push_str(&format!(
+            "{} merged ranges, {} ids in total\n",
+            input.len(),
+            input.cardinality()
+        ));
+
+        Ok(Some(out))
     }
 }
 
@@ -105,8 +174,8 @@ impl RangeHelper<u64> for RangeInclusive<u64> {
     }
 
     fn merge(self, other: Self) -> Self {
-        let start = std::cmp::min(self.start(), other.start());
-        let end = std::cmp::max(self.end(), other.end());
+        let start = core::cmp::min(self.start(), other.start());
+        let end = core::cmp::max(self.end(), other.end());
 
         Self::new(*start, *end)
     }
@@ -133,39 +202,29 @@ fn merge_overlapping(mut ranges: Vec<RangeInclusive<u64>>) -> Vec<RangeInclusive
     out
 }
 
-pub struct Two;
+/// Reference implementation of [`Two`], merging ranges until a fixpoint is reached.
+pub struct NaiveTwo;
 
-impl Puzzle for Two {
+impl Puzzle for NaiveTwo {
     type Input = Vec<RangeInclusive<u64>>;
-    type Output = u64;
+    type Output = <Two as Puzzle>::Output;
 
-    fn example_input() -> Self::Input {
-        let Database {
-            mut fresh_ingredients,
-            available_ingredients: _,
-        } = One::example_input();
+    const LAYOUT: Layout = Two::LAYOUT;
 
-        fresh_ingredients.drain().collect()
+    fn example_input() -> Self::Input {
+        One::example_input().fresh_ingredients
     }
 
     fn example_output() -> Self::Output {
-        14
+        Two::example_output()
     }
 
     fn input_file() -> &'static str {
-        One::input_file()
+        Two::input_file()
     }
 
-    fn parse_input<B>(reader: B) -> Result<Self::Input>
-    where
-        B: BufRead,
-    {
-        let Database {
-            mut fresh_ingredients,
-            available_ingredients: _,
-        } = One::parse_input(reader)?;
-
-        Ok(fresh_ingredients.drain().collect())
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        Ok(One::parse_input(input)?.fresh_ingredients)
     }
 
     fn solve(&mut self, mut input: Self::Input) -> Result<Self::Output> {
@@ -182,6 +241,63 @@ impl Puzzle for Two {
     }
 }
 
+impl Anonymizer for One {
+    /// Shuffles the ranges and the ingredients, moving both ends of a range by up to a tenth
+    /// of its length and every ingredient by up to a thousandth of its ID.
+    fn anonymize(text: &str, rng: &mut Rng) -> Result<String> {
+        let Database {
+            mut fresh_ingredients,
+            available_ingredients,
+        } = Self::parse_input(text.as_bytes())?;
+        rng.shuffle(&mut fresh_ingredients);
+
+        let mut out = String::new();
+
+        for range in fresh_ingredients {
+            let spread = (range.end() - range.start()) / 10;
+            let start = rng.jitter(*range.start(), spread);
+            let end = rng.jitter(*range.end(), spread).max(start);
+            out.push_str(&format!("{start}-{end}\n"));
+        }
+
+        out.push('\n');
+
+        let mut available: Vec<u64> = available_ingredients
+            .into_iter()
+            .map(|id| rng.jitter(id, id / 1000))
+            .collect();
+        rng.shuffle(&mut available);
+
+        for id in available {
+            out.push_str(&format!("{id}\n"));
+        }
+
+        Ok(out)
+    }
+}
+
+impl Generator for One {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        const MAX_ID: u64 = 100_000_000_000_000;
+
+        let max_len = 2 * MAX_ID / size.max(1) as u64;
+        let mut out = String::new();
+
+        for _ in 0..size {
+            let start = rng.range(1..=MAX_ID);
+            out.push_str(&format!("{start}-{}\n", start + rng.below(max_len)));
+        }
+
+        out.push('\n');
+
+        for _ in 0..size {
+            out.push_str(&format!("{}\n", rng.range(1..=MAX_ID)));
+        }
+
+        out
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -197,4 +313,10 @@ mod tests {
         let mut two = Two;
         two.test_example()
     }
+
+    #[test]
+    fn naive() -> Result<()> {
+        let mut two = NaiveTwo;
+        two.test_example()
+    }
 }
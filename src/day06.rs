@@ -1,6 +1,12 @@
+use crate::anonymize::Anonymizer;
+use crate::checked::{self, Integer};
+use crate::encode::{Decode, Encode, Reader};
+use crate::generate::{Generator, Rng};
+use crate::input;
+use crate::parse;
 use crate::prelude::*;
-use std::ops::{Add, Mul};
-use std::str::FromStr;
+use core::str::FromStr;
+use core::time::Duration;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Operator {
@@ -9,13 +15,10 @@ pub enum Operator {
 }
 
 impl Operator {
-    fn apply<T>(self, a: T, b: T) -> T
-    where
-        T: Add<Output = T> + Mul<Output = T>,
-    {
+    fn apply<T: Integer>(self, a: T, b: T) -> Result<T> {
         match self {
-            Self::Add => a + b,
-            Self::Mul => a * b,
+            Self::Add => checked::add("day06 sum", a, b),
+            Self::Mul => checked::mul("day06 product", a, b),
         }
     }
 }
@@ -32,12 +35,57 @@ impl FromStr for Operator {
     }
 }
 
+impl Encode for Operator {
+    fn encode(&self, out: &mut Vec<u8>) {
+        let tag: u8 = match self {
+            Self::Add => 0,
+            Self::Mul => 1,
+        };
+
+        tag.encode(out);
+    }
+}
+
+impl Decode<'_> for Operator {
+    fn decode(r: &mut Reader<'_>) -> Result<Self> {
+        match u8::decode(r)? {
+            0 => Ok(Self::Add),
+            1 => Ok(Self::Mul),
+            tag => Err(format!("not an operator tag: {tag}").into()),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Problem {
     numbers: Vec<u64>,
     operator: Operator,
 }
 
+impl Problem {
+    fn evaluate(&self) -> Result<u64> {
+        let (first, rest) = self.numbers.split_first().ok_or("empty problem")?;
+        rest.iter()
+            .try_fold(*first, |acc, e| self.operator.apply(acc, *e))
+    }
+}
+
+impl Encode for Problem {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.numbers.encode(out);
+        self.operator.encode(out);
+    }
+}
+
+impl Decode<'_> for Problem {
+    fn decode(r: &mut Reader<'_>) -> Result<Self> {
+        Ok(Self {
+            numbers: Vec::decode(r)?,
+            operator: Operator::decode(r)?,
+        })
+    }
+}
+
 #[derive(Default)]
 struct ProblemBuilder {
     numbers: Vec<u64>,
@@ -56,41 +104,35 @@ impl ProblemBuilder {
     }
 }
 
+pub const EXAMPLE: &str = include_str!("../examples/day06/example");
+
+const ANSWERS: &str = include_str!("../examples/day06/answers");
+
 pub struct One;
 
 impl Puzzle for One {
     type Input = Vec<Problem>;
     type Output = u64;
 
-    fn example_input() -> Self::Input {
-        let s = "123 328  51 64
- 45 64  387 23
-  6 98  215 314
-*   +   *   +
-"
-        .to_owned();
-
-        let reader = BufReader::new(s.as_bytes());
+    const LAYOUT: Layout = Layout::Lines;
+    const BUDGET: Duration = Duration::from_millis(5);
 
-        Self::parse_input(reader).unwrap()
+    fn example_input() -> Self::Input {
+        Self::parse_input(EXAMPLE.as_bytes()).unwrap()
     }
 
     fn example_output() -> Self::Output {
-        4277556
+        input::example_answer(ANSWERS, 1)
     }
 
     fn input_file() -> &'static str {
         "inputs/day06/input"
     }
 
-    fn parse_input<B>(reader: B) -> Result<Self::Input>
-    where
-        B: BufRead,
-    {
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
         let mut problems = Vec::new();
 
-        for line in reader.lines() {
-            let line = line?;
+        for line in parse::text(input)?.lines() {
             let trimmed = line.trim();
 
             for (column, component) in trimmed.split_whitespace().enumerate() {
@@ -125,16 +167,15 @@ impl Puzzle for One {
         let mut total = 0;
 
         for problem in input {
-            total += problem
-                .numbers
-                .iter()
-                .copied()
-                .reduce(|acc, e| problem.operator.apply(acc, e))
-                .unwrap();
+            total = checked::add("day06 total", total, problem.evaluate()?)?;
         }
 
         Ok(total)
     }
+
+    fn summary(input: &Self::Input) -> Option<String> {
+        Some(format!("{} problems", input.len()))
+    }
 }
 
 pub struct Two;
@@ -143,36 +184,25 @@ impl Puzzle for Two {
     type Input = Vec<Problem>;
     type Output = u64;
 
-    fn example_input() -> Self::Input {
-        let s = "123 328  51 64
- 45 64  387 23
-  6 98  215 314
-*   +   *   +
-"
-        .to_owned();
-
-        let reader = BufReader::new(s.as_bytes());
+    const LAYOUT: Layout = Layout::Grid;
+    const BUDGET: Duration = Duration::from_millis(5);
 
-        Self::parse_input(reader).unwrap()
+    fn example_input() -> Self::Input {
+        Self::parse_input(EXAMPLE.as_bytes()).unwrap()
     }
 
     fn example_output() -> Self::Output {
-        3263827
+        input::example_answer(ANSWERS, 2)
     }
 
     fn input_file() -> &'static str {
         One::input_file()
     }
 
-    fn parse_input<B>(reader: B) -> Result<Self::Input>
-    where
-        B: BufRead,
-    {
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
         let mut columns = Vec::new();
 
-        for line in reader.lines() {
-            let line = line?;
-
+        for line in parse::text(input)?.lines() {
             for (column, c) in line.chars().enumerate() {
                 if column >= columns.len() {
                     columns.push(String::from(c));
@@ -191,7 +221,7 @@ impl Puzzle for Two {
         let mut builder = ProblemBuilder::default();
         for column in columns {
             if column.chars().all(char::is_whitespace) {
-                let tmp = std::mem::take(&mut builder);
+                let tmp = core::mem::take(&mut builder);
                 problems.push(tmp.build()?);
                 continue;
             }
@@ -213,14 +243,66 @@ impl Puzzle for Two {
     }
 
     fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
-        Ok(input.iter().fold(0, |acc, problem| {
-            acc + problem
-                .numbers
-                .iter()
-                .copied()
-                .reduce(|acc, e| problem.operator.apply(acc, e))
-                .unwrap()
-        }))
+        input.iter().try_fold(0, |acc, problem| {
+            checked::add("day06 total", acc, problem.evaluate()?)
+        })
+    }
+}
+
+impl Anonymizer for One {
+    /// Replaces every digit with a random nonzero one and flips every operator with even odds,
+    /// so the columns stay aligned.
+    fn anonymize(text: &str, rng: &mut Rng) -> Result<String> {
+        Self::parse_input(text.as_bytes())?;
+
+        Ok(text
+            .chars()
+            .map(|c| match c {
+                '0'..='9' => char::from(b'1' + rng.below(9) as u8),
+                '+' if rng.chance(1, 2) => '*',
+                '*' if rng.chance(1, 2) => '+',
+                c => c,
+            })
+            .collect())
+    }
+}
+
+impl Generator for One {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let rows = rng.range(2..=4) as usize;
+        let mut lines = vec![String::new(); rows + 1];
+
+        for problem in 0..size.max(1) {
+            if problem > 0 {
+                for line in lines.iter_mut() {
+                    line.push(' ');
+                }
+            }
+
+            let numbers: Vec<String> = (0..rows)
+                .map(|_| {
+                    (0..rng.range(1..=4))
+                        .map(|_| char::from(b'1' + rng.below(9) as u8))
+                        .collect()
+                })
+                .collect();
+
+            let width = numbers.iter().map(String::len).max().unwrap_or(0);
+            let left_aligned = rng.chance(1, 2);
+
+            for (line, number) in lines.iter_mut().zip(numbers) {
+                if left_aligned {
+                    line.push_str(&format!("{number:<width$}"));
+                } else {
+                    line.push_str(&format!("{number:>width$}"));
+                }
+            }
+
+            let operator = if rng.chance(1, 2) { '+' } else { '*' };
+            lines[rows].push_str(&format!("{operator:<width$}"));
+        }
+
+        lines.iter().map(|line| format!("{line}\n")).collect()
     }
 }
 
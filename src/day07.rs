@@ -1,153 +1,314 @@
+use crate::anonymize::Anonymizer;
+use crate::encode::{Decode, Encode, Reader};
+use crate::generate::{Generator, Rng};
+use crate::graph::Graph;
+use crate::grid::Grid;
+use crate::input;
 use crate::prelude::*;
-use std::collections::{HashMap, HashSet};
-use std::hash::Hash;
+#[cfg(feature = "std")]
+use crate::render::{Color, Scene};
+use crate::simulation::{DynSimulation, Simulation};
+use alloc::collections::BTreeMap;
+use core::fmt;
+use core::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    Start,
+    Splitter,
+}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Point {
-    x: u32,
-    y: u32,
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "."),
+            Self::Start => write!(f, "S"),
+            Self::Splitter => write!(f, "^"),
+        }
+    }
 }
 
-#[derive(Debug)]
+impl Encode for Cell {
+    fn encode(&self, out: &mut Vec<u8>) {
+        let tag: u8 = match self {
+            Self::Empty => 0,
+            Self::Start => 1,
+            Self::Splitter => 2,
+        };
+
+        tag.encode(out);
+    }
+}
+
+impl Decode<'_> for Cell {
+    fn decode(r: &mut Reader<'_>) -> Result<Self> {
+        match u8::decode(r)? {
+            0 => Ok(Self::Empty),
+            1 => Ok(Self::Start),
+            2 => Ok(Self::Splitter),
+            tag => Err(format!("not a cell tag: {tag}").into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Manifold {
-    start: Point,
-    splitters: HashSet<Point>,
-    size: Point,
+    start: (usize, usize),
+    cells: Grid<Cell>,
+}
+
+impl Encode for Manifold {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.start.encode(out);
+        self.cells.encode(out);
+    }
+}
+
+impl Decode<'_> for Manifold {
+    fn decode(r: &mut Reader<'_>) -> Result<Self> {
+        let (x, y) = Decode::decode(r)?;
+        let cells = Grid::decode(r)?;
+
+        if cells.get(x, y) != Some(&Cell::Start) {
+            return Err("corrupt manifold encoding".into());
+        }
+
+        Ok(Self {
+            start: (x, y),
+            cells,
+        })
+    }
 }
 
 impl Manifold {
-    fn is_leaf(&self, pt: Point) -> bool {
-        pt.y == self.size.y - 1
+    fn is_splitter(&self, x: usize, y: usize) -> bool {
+        self.cells[(x, y)] == Cell::Splitter
+    }
+
+    /// The first splitter a beam entering `(x, y)` from above hits below that cell.
+    fn splitter_below(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        (y + 1..self.cells.height())
+            .map(|y| (x, y))
+            .find(|(x, y)| self.is_splitter(*x, *y))
+    }
+
+    /// The splitters reachable from the start as a DAG, with an edge for every beam between
+    /// them and one to a sink node for every beam leaving the bottom. Returns the graph, the
+    /// start node and the sink.
+    fn graph(&self) -> (Graph, usize, usize) {
+        let mut graph = Graph::default();
+        let start = graph.add_node();
+        let sink = graph.add_node();
+
+        let mut nodes = BTreeMap::new();
+        let mut pending = vec![(start, self.start)];
+
+        while let Some((node, (x, y))) = pending.pop() {
+            let beams = if node == start {
+                vec![x]
+            } else {
+                [
+                    x.checked_sub(1),
+                    Some(x + 1).filter(|x| *x < self.cells.width()),
+                ]
+                .into_iter()
+                .flatten()
+                .collect()
+            };
+
+            for x in beams {
+                let next = match self.splitter_below(x, y) {
+                    Some(splitter) => *nodes.entry(splitter).or_insert_with(|| {
+                        let next = graph.add_node();
+                        pending.push((next, splitter));
+                        next
+                    }),
+                    None => sink,
+                };
+
+                graph.add_edge(node, next);
+            }
+        }
+
+        (graph, start, sink)
     }
 }
 
-#[derive(Default)]
-struct ManifoldBuilder {
-    start: Option<Point>,
-    splitters: HashSet<Point>,
-    size: Option<Point>,
+/// Propagates the beams one row further down per step, counting the splitters they hit.
+pub struct Beams {
+    manifold: Manifold,
+    beams: Grid<bool>,
+    row: usize,
+    used_splitters: usize,
 }
 
-impl ManifoldBuilder {
-    fn build(self) -> Result<Manifold> {
-        Ok(Manifold {
-            start: self.start.ok_or("invalid input")?,
-            splitters: self.splitters,
-            size: self.size.ok_or("invalid input")?,
-        })
+impl Beams {
+    pub fn new(manifold: Manifold) -> Self {
+        let mut beams = Grid::new(manifold.cells.width(), manifold.cells.height(), false);
+        beams[manifold.start] = true;
+
+        Self {
+            manifold,
+            beams,
+            row: 0,
+            used_splitters: 0,
+        }
     }
 }
 
+impl fmt::Display for Beams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.beams.height() {
+            for x in 0..self.beams.width() {
+                match self.manifold.cells[(x, y)] {
+                    Cell::Empty if self.beams[(x, y)] => write!(f, "|")?,
+                    cell => write!(f, "{cell}")?,
+                }
+            }
+
+            writeln!(f)?;
+        }
+
+        write!(f, "{} splitters used", self.used_splitters)
+    }
+}
+
+impl Simulation for Beams {
+    type State = Self;
+
+    fn state(&self) -> &Self {
+        self
+    }
+
+    fn step(&mut self) {
+        if self.is_done() {
+            return;
+        }
+
+        let y = self.row + 1;
+        let width = self.beams.width();
+
+        for x in 0..width {
+            if !self.beams[(x, y - 1)] {
+                continue;
+            }
+
+            if self.manifold.is_splitter(x, y) {
+                if x > 0 {
+                    self.beams[(x - 1, y)] = true;
+                }
+
+                if x < width - 1 {
+                    self.beams[(x + 1, y)] = true;
+                }
+
+                self.used_splitters += 1;
+            } else {
+                self.beams[(x, y)] = true;
+            }
+        }
+
+        self.row = y;
+    }
+
+    fn is_done(&self) -> bool {
+        self.row + 1 >= self.beams.height()
+    }
+
+    fn steps(&self) -> usize {
+        self.row
+    }
+}
+
+pub const EXAMPLE: &str = include_str!("../examples/day07/example");
+
+const ANSWERS: &str = include_str!("../examples/day07/answers");
+
 pub struct One;
 
 impl Puzzle for One {
     type Input = Manifold;
     type Output = usize;
 
+    const LAYOUT: Layout = Layout::Grid;
+    const BUDGET: Duration = Duration::from_millis(5);
+
     fn example_input() -> Self::Input {
-        let s = ".......S.......
-...............
-.......^.......
-...............
-......^.^......
-...............
-.....^.^.^.....
-...............
-....^.^...^....
-...............
-...^.^...^.^...
-...............
-..^...^.....^..
-...............
-.^.^.^.^.^...^.
-...............
-";
-
-        let reader = BufReader::new(s.as_bytes());
-        Self::parse_input(reader).unwrap()
+        Self::parse_input(EXAMPLE.as_bytes()).unwrap()
     }
 
     fn example_output() -> Self::Output {
-        21
+        input::example_answer(ANSWERS, 1)
     }
 
     fn input_file() -> &'static str {
         "inputs/day07/input"
     }
 
-    fn parse_input<B>(reader: B) -> Result<Self::Input>
-    where
-        B: BufRead,
-    {
-        let mut manifold = ManifoldBuilder::default();
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        let cells = Grid::parse(input, |c| match c {
+            'S' => Ok(Cell::Start),
+            '^' => Ok(Cell::Splitter),
+            _ => Ok(Cell::Empty),
+        })?;
 
-        let mut x = 0;
-        let mut y = 0;
+        let start = cells
+            .positions()
+            .find(|pos| cells[*pos] == Cell::Start)
+            .ok_or("invalid input")?;
 
-        for line in reader.lines() {
-            for c in line?.chars() {
-                match c {
-                    'S' => manifold.start = Some(Point { x, y }),
-                    '^' => {
-                        manifold.splitters.insert(Point { x, y });
-                    }
-                    _ => (),
-                }
-
-                x += 1;
-
-                if x > manifold.size.map(|pt| pt.x).unwrap_or(0) {
-                    manifold.size = Some(Point { x, y });
-                }
-            }
-
-            x = 0;
-            y += 1;
-        }
-
-        manifold.size.as_mut().unwrap().y = y;
-
-        manifold.build()
+        Ok(Manifold { start, cells })
     }
 
     fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
-        let mut beam_points = HashSet::new();
-        beam_points.insert(input.start);
-
-        let mut used_splitters = HashSet::new();
+        let mut beams = Beams::new(input);
+        beams.run();
 
-        let mut x = 0;
-        let mut y = 1;
+        Ok(beams.used_splitters)
+    }
 
-        while x < input.size.x && y < input.size.y {
-            let point = Point { x, y };
-            let point_above = Point { x, y: y - 1 };
+    fn summary(input: &Self::Input) -> Option<String> {
+        Some(format!(
+            "{}x{} manifold with {} splitters",
+            input.cells.width(),
+            input.cells.height(),
+            input
+                .cells
+                .iter()
+                .filter(|cell| **cell == Cell::Splitter)
+                .count()
+        ))
+    }
 
-            if beam_points.contains(&point_above) {
-                if input.splitters.contains(&point) {
-                    if x > 0 {
-                        beam_points.insert(Point { x: x - 1, y });
-                    }
+    /// Beams in cyan, splitters they hit in yellow and the others in grey.
+    #[cfg(feature = "std")]
+    fn render(input: &Self::Input) -> Option<Scene> {
+        let mut beams = Beams::new(input.clone());
+        beams.run();
 
-                    if x < input.size.x - 1 {
-                        beam_points.insert(Point { x: x + 1, y });
-                    }
+        let beams = beams.beams;
+        let mut scene = Scene::new(input.cells.width() as u64, input.cells.height() as u64);
 
-                    used_splitters.insert(point);
-                } else {
-                    beam_points.insert(point);
-                }
-            }
+        for (x, y) in input.cells.positions() {
+            let hit = y > 0 && beams[(x, y - 1)];
 
-            x += 1;
+            let (glyph, color) = match input.cells[(x, y)] {
+                Cell::Start => ('S', Color::GREEN),
+                Cell::Splitter if hit => ('^', Color::YELLOW),
+                Cell::Splitter => ('^', Color::GREY),
+                Cell::Empty if beams[(x, y)] => ('|', Color::CYAN),
+                Cell::Empty => continue,
+            };
 
-            if x == input.size.x {
-                x = 0;
-                y += 1;
-            }
+            scene.cell(x as u64, y as u64, glyph, color);
         }
 
-        Ok(used_splitters.len())
+        Some(scene)
+    }
+
+    fn simulation(input: Self::Input) -> Option<DynSimulation> {
+        Some(Box::new(Beams::new(input)))
     }
 }
 
@@ -157,65 +318,142 @@ impl Puzzle for Two {
     type Input = Manifold;
     type Output = usize;
 
+    const LAYOUT: Layout = One::LAYOUT;
+    const BUDGET: Duration = Duration::from_millis(5);
+
     fn example_input() -> Self::Input {
         One::example_input()
     }
 
     fn example_output() -> Self::Output {
-        40
+        input::example_answer(ANSWERS, 2)
     }
 
     fn input_file() -> &'static str {
         One::input_file()
     }
 
-    fn parse_input<B>(reader: B) -> Result<Self::Input>
-    where
-        B: BufRead,
-    {
-        One::parse_input(reader)
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        One::parse_input(input)
     }
 
     fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
-        let mut beam_points = HashMap::new();
-        beam_points.insert(input.start, 1);
+        let (graph, start, sink) = input.graph();
 
-        let mut x = 0;
-        let mut y = 1;
+        Ok(graph.count_paths(start, sink)?.try_into()?)
+    }
+}
 
-        while x < input.size.x && y < input.size.y {
-            let point = Point { x, y };
-            let point_above = Point { x, y: y - 1 };
+/// Reference implementation of [`Two`], sweeping timeline counts down the grid row by row.
+pub struct SweepTwo;
 
-            if let Some(count) = beam_points.get(&point_above).copied() {
-                if input.splitters.contains(&point) {
-                    if x > 0 {
-                        let down_left = Point { x: x - 1, y };
-                        *beam_points.entry(down_left).or_insert(0) += count;
-                    }
+impl Puzzle for SweepTwo {
+    type Input = <Two as Puzzle>::Input;
+    type Output = <Two as Puzzle>::Output;
+
+    const LAYOUT: Layout = Two::LAYOUT;
+
+    fn example_input() -> Self::Input {
+        Two::example_input()
+    }
+
+    fn example_output() -> Self::Output {
+        Two::example_output()
+    }
+
+    fn input_file() -> &'static str {
+        Two::input_file()
+    }
+
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        Two::parse_input(input)
+    }
+
+    fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
+        let (width, height) = (input.cells.width(), input.cells.height());
+
+        let mut timelines = Grid::new(width, height, 0);
+        timelines[input.start] = 1;
+
+        for (x, y) in input.cells.positions().filter(|(_, y)| *y > 0) {
+            let count = timelines[(x, y - 1)];
+
+            if count == 0 {
+                continue;
+            }
+
+            if input.is_splitter(x, y) {
+                if x > 0 {
+                    timelines[(x - 1, y)] += count;
+                }
+
+                if x < width - 1 {
+                    timelines[(x + 1, y)] += count;
+                }
+            } else {
+                timelines[(x, y)] += count;
+            }
+        }
+
+        Ok(timelines.row(height - 1).iter().sum())
+    }
+}
+
+impl Anonymizer for One {
+    /// Mirrors the manifold with even odds, then removes one splitter in ten and adds about as
+    /// many on free cells of the same rows, keeping the spacing of the splitters in each row.
+    fn anonymize(text: &str, rng: &mut Rng) -> Result<String> {
+        Self::parse_input(text.as_bytes())?;
+
+        let mirror = rng.chance(1, 2);
+        let mut out = String::new();
 
-                    if x < input.size.x - 1 {
-                        let down_right = Point { x: x + 1, y };
-                        *beam_points.entry(down_right).or_insert(0) += count;
+        for row in text.lines() {
+            let mut row: Vec<char> = row.chars().collect();
+
+            if mirror {
+                row.reverse();
+            }
+
+            if let Some(first) = row.iter().position(|c| *c == '^') {
+                for (x, c) in row.iter_mut().enumerate() {
+                    match c {
+                        '^' if rng.chance(1, 10) => *c = '.',
+                        '.' if x % 2 == first % 2 && rng.chance(1, 10) => *c = '^',
+                        _ => (),
                     }
-                } else {
-                    *beam_points.entry(point).or_insert(0) += count;
                 }
             }
 
-            x += 1;
+            out.extend(row);
+            out.push('\n');
+        }
+
+        Ok(out)
+    }
+}
 
-            if x == input.size.x {
-                x = 0;
-                y += 1;
+impl Generator for One {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let width = 2 * size.max(1) + 1;
+        let height = 2 * size.max(1);
+        let mut out = String::new();
+
+        for y in 0..height {
+            for x in 0..width {
+                let c = match (x, y) {
+                    (x, 0) if x == width / 2 => 'S',
+                    (_, y) if y > 0 && y % 2 == 0 && rng.chance(1, 3) => '^',
+                    _ => '.',
+                };
+
+                out.push(c);
             }
+
+            out.push('\n');
         }
 
-        Ok(beam_points
-            .iter()
-            .filter(|(point, _)| input.is_leaf(**point))
-            .map(|(_, count)| count)
-            .sum())
+        out
     }
 }
 
@@ -234,4 +472,10 @@ mod tests {
         let mut two = Two;
         two.test_example()
     }
+
+    #[test]
+    fn sweep() -> Result<()> {
+        let mut two = SweepTwo;
+        two.test_example()
+    }
 }
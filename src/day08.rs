@@ -1,211 +1,236 @@
+use crate::anonymize::Anonymizer;
+use crate::checked;
+use crate::disjoint_set::DisjointSet;
+use crate::generate::{Generator, Rng};
+use crate::geometry::{Bounds3, Point3};
+use crate::input;
+use crate::parse::{self, Scanner};
 use crate::prelude::*;
-use std::collections::HashSet;
-use std::hash::Hash;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Point {
-    x: u64,
-    y: u64,
-    z: u64,
-}
+use alloc::collections::BTreeSet;
+use core::time::Duration;
 
-impl Point {
-    fn distance(self, other: Self) -> u64 {
-        ((self.x.abs_diff(other.x)).pow(2)
-            + (self.y.abs_diff(other.y)).pow(2)
-            + (self.z.abs_diff(other.z)).pow(2))
-        .isqrt()
-    }
-}
+pub type Point = Point3<u64>;
+
+fn distance(a: Point, b: Point) -> Result<u64> {
+    const CONTEXT: &str = "day08 distance";
+
+    let d = a.abs_diff(b);
+    let x = checked::pow(CONTEXT, d.x, 2)?;
+    let y = checked::pow(CONTEXT, d.y, 2)?;
+    let z = checked::pow(CONTEXT, d.z, 2)?;
 
-fn build_distances(input: &HashSet<Point>) -> Vec<(Point, Point, u64)> {
-    let mut distances = Vec::new();
-    let mut connections = HashSet::new();
+    Ok(checked::add(CONTEXT, checked::add(CONTEXT, x, y)?, z)?.isqrt())
+}
 
-    for a in input.iter() {
-        for b in input.iter() {
-            if a == b || connections.contains(&(b, a)) {
-                continue;
-            }
+/// Every pair of points as indices into `points`, closest first.
+fn build_distances(points: &[Point]) -> Result<Vec<(usize, usize, u64)>> {
+    let mut distances = Vec::with_capacity(points.len() * points.len().saturating_sub(1) / 2);
 
-            distances.push((*a, *b, a.distance(*b)));
-            connections.insert((a, b));
+    for (i, a) in points.iter().enumerate() {
+        for (j, b) in points.iter().enumerate().skip(i + 1) {
+            distances.push((i, j, distance(*a, *b)?));
         }
     }
 
     distances.sort_by_key(|(_, _, distance)| *distance);
-    distances
+    Ok(distances)
 }
 
-fn add_cluster(clusters: &mut Vec<HashSet<Point>>, a: Point, b: Point) {
-    let mut first_cluster = None;
-    let mut second_cluster = None;
-
-    for (idx, cluster) in clusters.iter().enumerate() {
-        if cluster.contains(&a) || cluster.contains(&b) {
-            if first_cluster.is_none() {
-                first_cluster = Some(idx);
-            } else if second_cluster.is_none() {
-                second_cluster = Some(idx);
-                break;
-            }
-        }
-    }
+/// Number of closest pairs part one connects. The example connects only ten.
+const CONNECTIONS: usize = 1000;
 
-    match (first_cluster, second_cluster) {
-        (None, None) => {
-            let mut cluster = HashSet::new();
-            cluster.insert(a);
-            cluster.insert(b);
+/// The clusters formed by connecting the `n` closest pairs of points.
+fn closest_clusters(points: &[Point], n: usize) -> Result<DisjointSet> {
+    let mut clusters = DisjointSet::new(points.len());
 
-            clusters.push(cluster);
-        }
+    for (a, b, _) in build_distances(points)?.into_iter().take(n) {
+        clusters.union(a, b);
+    }
 
-        (Some(idx), None) => {
-            clusters[idx].insert(a);
-            clusters[idx].insert(b);
-        }
+    Ok(clusters)
+}
 
-        (Some(idx_a), Some(idx_b)) => {
-            let mut to_merge = clusters.remove(idx_b);
+/// The product of the sizes of the three largest clusters.
+fn largest_product(clusters: &DisjointSet) -> Result<usize> {
+    let mut sizes: Vec<usize> = clusters.sizes().collect();
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
 
-            clusters[idx_a].extend(to_merge.drain());
+    Ok(sizes
+        .into_iter()
+        .take(3)
+        .reduce(|acc, e| acc * e)
+        .ok_or("not enough clusters")?)
+}
 
-            clusters[idx_a].insert(a);
-            clusters[idx_a].insert(b);
-        }
+/// The connection, closest pairs first, that joins all points into one cluster.
+fn last_connection(points: &[Point]) -> Result<(usize, usize, u64)> {
+    let mut clusters = DisjointSet::new(points.len());
 
-        _ => unreachable!(),
+    for (a, b, distance) in build_distances(points)? {
+        if clusters.union(a, b) && clusters.count() == 1 {
+            return Ok((a, b, distance));
+        }
     }
+
+    Err("no solution found".into())
 }
 
+pub const EXAMPLE: &str = include_str!("../examples/day08/example");
+
+const ANSWERS: &str = include_str!("../examples/day08/answers");
+
 pub struct One;
 
 impl Puzzle for One {
-    type Input = HashSet<Point>;
+    type Input = BTreeSet<Point>;
     type Output = usize;
 
+    const LAYOUT: Layout = Layout::Lines;
+    const BUDGET: Duration = Duration::from_millis(150);
+
     fn example_input() -> Self::Input {
-        let s = "162,817,812
-57,618,57
-906,360,560
-592,479,940
-352,342,300
-466,668,158
-542,29,236
-431,825,988
-739,650,466
-52,470,668
-216,146,977
-819,987,18
-117,168,530
-805,96,715
-346,949,466
-970,615,88
-941,993,340
-862,61,35
-984,92,344
-425,690,689
-"
-        .to_owned();
-
-        let reader = BufReader::new(s.as_bytes());
-
-        Self::parse_input(reader).unwrap()
+        Self::parse_input(EXAMPLE.as_bytes()).unwrap()
     }
 
     fn example_output() -> Self::Output {
-        40
+        input::example_answer(ANSWERS, 1)
     }
 
     fn input_file() -> &'static str {
         "inputs/day08/input"
     }
 
-    fn parse_input<B>(reader: B) -> Result<Self::Input>
-    where
-        B: BufRead,
-    {
-        let mut junction_boxes = HashSet::new();
-
-        for line in reader.lines() {
-            let line = line?;
-            let mut iter = line.trim().split(',');
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        let text = parse::text(input)?;
+        let mut s = Scanner::new(text);
+        let junction_boxes = s.lines(|s| {
+            let [x, y, z] = s.tuple(',', Scanner::unsigned)?;
+            Ok(Point::new(x, y, z))
+        })?;
+        s.finish()?;
 
-            let x = iter.next().ok_or("invalid input")?.parse()?;
-            let y = iter.next().ok_or("invalid input")?.parse()?;
-            let z = iter.next().ok_or("invalid input")?.parse()?;
-
-            junction_boxes.insert(Point { x, y, z });
-        }
-
-        Ok(junction_boxes)
+        Ok(junction_boxes.into_iter().collect())
     }
 
     fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
-        let n = if cfg!(test) { 10 } else { 1000 };
+        let points: Vec<Point> = input.into_iter().collect();
+        largest_product(&closest_clusters(&points, CONNECTIONS)?)
+    }
+
+    fn explain(&mut self, input: Self::Input) -> Result<Option<String>> {
+        let points: Vec<Point> = input.into_iter().collect();
+        let mut components = closest_clusters(&points, CONNECTIONS)?.components();
+        components.sort_by_key(|component| core::cmp::Reverse(component.len()));
 
-        let distances = build_distances(&input);
-        let mut clusters: Vec<HashSet<Point>> = Vec::new();
+        let mut out = String::new();
 
-        for (a, b, _) in distances.iter().take(n) {
-            add_cluster(&mut clusters, *a, *b);
+        for component in components.iter().take(3) {
+            let members: Vec<String> = component.iter().map(|i| points[*i].to_string()).collect();
+            out.push_str(&format!(
+                "cluster of {}: {}\n",
+                component.len(),
+                members.join(" ")
+            ));
         }
 
-        clusters.sort_by_key(|cluster| -(cluster.len() as i32));
+        Ok(Some(out))
+    }
+
+    fn summary(input: &Self::Input) -> Option<String> {
+        let Bounds3 { min, max } = Bounds3::from_points(input.iter().copied())?;
 
-        Ok(clusters
-            .iter()
-            .map(HashSet::len)
-            .take(3)
-            .reduce(|acc, e| acc * e)
-            .ok_or("not enough clusters")?)
+        Some(format!(
+            "{} points within x {}..={}, y {}..={}, z {}..={}",
+            input.len(),
+            min.x,
+            max.x,
+            min.y,
+            max.y,
+            min.z,
+            max.z
+        ))
     }
 }
 
 pub struct Two;
 
 impl Puzzle for Two {
-    type Input = HashSet<Point>;
+    type Input = BTreeSet<Point>;
     type Output = u64;
 
+    const LAYOUT: Layout = One::LAYOUT;
+    const BUDGET: Duration = Duration::from_millis(150);
+
     fn example_input() -> Self::Input {
         One::example_input()
     }
 
     fn example_output() -> Self::Output {
-        25272
+        input::example_answer(ANSWERS, 2)
     }
 
     fn input_file() -> &'static str {
         One::input_file()
     }
 
-    fn parse_input<B>(reader: B) -> Result<Self::Input>
-    where
-        B: BufRead,
-    {
-        One::parse_input(reader)
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        One::parse_input(input)
     }
 
     fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
-        let distances = build_distances(&input);
-        let mut clusters: Vec<HashSet<Point>> = Vec::new();
-
-        for (i, (a, b, _)) in distances.iter().enumerate() {
-            add_cluster(&mut clusters, *a, *b);
-
-            // avoid checking if a solution has been reached before a minimum of points processed
-            if i >= input.len() {
-                for cluster in clusters.iter() {
-                    if cluster.len() == input.len() {
-                        return Ok(a.x * b.x);
-                    }
-                }
-            }
-        }
+        let points: Vec<Point> = input.into_iter().collect();
+        let (a, b, _) = last_connection(&points)?;
 
-        Err("no solution found".into())
+        checked::mul("day08 answer", points[a].x, points[b].x)
+    }
+
+    fn explain(&mut self, input: Self::Input) -> Result<Option<String>> {
+        let points: Vec<Point> = input.into_iter().collect();
+        let (a, b, distance) = last_connection(&points)?;
+
+        Ok(Some(format!(
+            "connecting {} and {} at distance {distance} joins all {} points\n",
+            points[a],
+            points[b],
+            points.len()
+        )))
+    }
+}
+
+impl Anonymizer for One {
+    /// Shuffles the junction boxes and moves every coordinate by up to a hundredth of the
+    /// largest one.
+    fn anonymize(text: &str, rng: &mut Rng) -> Result<String> {
+        let boxes = Self::parse_input(text.as_bytes())?;
+        let largest = boxes.iter().map(|p| p.x.max(p.y).max(p.z)).max();
+        let spread = largest.unwrap_or(0) / 100;
+
+        let mut boxes: Vec<Point> = boxes
+            .into_iter()
+            .map(|p| {
+                Point::new(
+                    rng.jitter(p.x, spread),
+                    rng.jitter(p.y, spread),
+                    rng.jitter(p.z, spread),
+                )
+            })
+            .collect();
+        rng.shuffle(&mut boxes);
+
+        Ok(boxes.iter().map(|p| format!("{p}\n")).collect())
+    }
+}
+
+impl Generator for One {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                let x = rng.below(100_000);
+                let y = rng.below(100_000);
+                let z = rng.below(100_000);
+                format!("{x},{y},{z}\n")
+            })
+            .collect()
     }
 }
 
@@ -215,8 +240,11 @@ mod tests {
 
     #[test]
     fn one() -> Result<()> {
-        let mut one = One;
-        one.test_example()
+        let points: Vec<Point> = One::example_input().into_iter().collect();
+        let clusters = closest_clusters(&points, 10)?;
+
+        assert_eq!(largest_product(&clusters)?, One::example_output());
+        Ok(())
     }
 
     #[test]
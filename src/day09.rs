@@ -1,10 +1,15 @@
+use crate::anonymize::Anonymizer;
+use crate::checked;
+use crate::generate::{Generator, Rng};
+use crate::geometry::{Bounds2, Point2};
+use crate::input;
+use crate::parse::{self, Scanner};
 use crate::prelude::*;
+#[cfg(feature = "std")]
+use crate::render::{Color, Scene};
+use core::time::Duration;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Point {
-    x: u64,
-    y: u64,
-}
+pub type Point = Point2<u64>;
 
 #[derive(Debug)]
 struct Edge {
@@ -17,36 +22,13 @@ impl Edge {
         self.a.y == self.b.y
     }
 
-    fn min_x(&self) -> u64 {
-        self.a.x.min(self.b.x)
-    }
-
-    fn max_x(&self) -> u64 {
-        self.a.x.max(self.b.x)
-    }
-
-    fn min_y(&self) -> u64 {
-        self.a.y.min(self.b.y)
-    }
-
-    fn max_y(&self) -> u64 {
-        self.a.y.max(self.b.y)
-    }
-
-    fn includes(&self, point: Point) -> bool {
-        if self.horizontal() {
-            self.a.y == point.y && point.x >= self.min_x() && point.x <= self.max_x()
-        } else {
-            self.a.x == point.x && point.y >= self.min_y() && point.y <= self.max_y()
-        }
+    fn bounds(&self) -> Bounds2<u64> {
+        Bounds2::new(self.a, self.b)
     }
 }
 
 #[derive(Debug, Clone, Copy)]
-struct Rectangle {
-    a: Point,
-    b: Point,
-}
+struct Rectangle(Bounds2<u64>);
 
 impl Rectangle {
     /// Make a rectangle from two diagonally opposite points.
@@ -61,162 +43,278 @@ impl Rectangle {
     /// +----------b
     /// ```
     fn from_points(a: Point, b: Point) -> Self {
-        Self { a, b }
+        Self(Bounds2::new(a, b))
     }
 
     fn corners(&self) -> [Point; 4] {
-        [
-            self.a,
-            self.b,
-            Point {
-                x: self.a.x,
-                y: self.b.y,
-            },
-            Point {
-                x: self.b.x,
-                y: self.a.y,
-            },
-        ]
+        self.0.corners()
     }
 
-    fn min_x(&self) -> u64 {
-        self.a.x.min(self.b.x)
-    }
+    fn intersects(&self, edge: &Edge) -> bool {
+        let Bounds2 { min, max } = self.0;
+        let line = edge.bounds();
 
-    fn min_y(&self) -> u64 {
-        self.a.y.min(self.b.y)
+        if edge.horizontal() {
+            min.y < edge.a.y && max.y > edge.a.y && min.x < line.max.x && max.x > line.min.x
+        } else {
+            min.x < edge.a.x && max.x > edge.a.x && min.y < line.max.y && max.y > line.min.y
+        }
     }
 
-    fn max_x(&self) -> u64 {
-        self.a.x.max(self.b.x)
-    }
+    fn size(&self) -> Result<u64> {
+        const CONTEXT: &str = "day09 rectangle size";
 
-    fn max_y(&self) -> u64 {
-        self.a.y.max(self.b.y)
+        let width = checked::add(CONTEXT, self.0.width(), 1)?;
+        let height = checked::add(CONTEXT, self.0.height(), 1)?;
+        checked::mul(CONTEXT, width, height)
     }
+}
 
-    fn intersects(&self, edge: &Edge) -> bool {
-        if edge.horizontal() {
-            self.min_y() < edge.a.y
-                && self.max_y() > edge.a.y
-                && self.min_x() < edge.a.x.max(edge.b.x)
-                && self.max_x() > edge.a.x.min(edge.b.x)
-        } else {
-            self.min_x() < edge.a.x
-                && self.max_x() > edge.a.x
-                && self.min_y() < edge.a.y.max(edge.b.y)
-                && self.max_y() > edge.a.y.min(edge.b.y)
-        }
-    }
+pub const EXAMPLE: &str = include_str!("../examples/day09/example");
 
-    fn size(&self) -> u64 {
-        (self.a.x.abs_diff(self.b.x) + 1) * (self.a.y.abs_diff(self.b.y) + 1)
+const ANSWERS: &str = include_str!("../examples/day09/answers");
+
+/// The corners of the chosen rectangle.
+fn explain(rectangle: Option<Rectangle>) -> Result<String> {
+    match rectangle {
+        Some(rectangle) => {
+            let corners: Vec<String> = rectangle.corners().iter().map(Point::to_string).collect();
+            Ok(format!(
+                "rectangle with corners {} covers {} tiles\n",
+                corners.join(" "),
+                rectangle.size()?
+            ))
+        }
+        None => Ok("no rectangle fits\n".to_owned()),
     }
 }
 
 pub struct One;
 
+impl One {
+    /// The largest rectangle with opposite corners on `points`.
+    fn largest(points: &[Point]) -> Result<Option<Rectangle>> {
+        let mut max: Option<Rectangle> = None;
+        let mut max_size = 0;
+
+        for a in points.iter() {
+            for b in points.iter() {
+                let rectangle = Rectangle::from_points(*a, *b);
+                let size = rectangle.size()?;
+
+                if size > max_size {
+                    max = Some(rectangle);
+                    max_size = size;
+                }
+            }
+        }
+
+        Ok(max)
+    }
+}
+
 impl Puzzle for One {
     type Input = Vec<Point>;
     type Output = u64;
 
+    const LAYOUT: Layout = Layout::Lines;
+    const BUDGET: Duration = Duration::from_millis(5);
+
     fn example_input() -> Self::Input {
-        vec![
-            Point { x: 7, y: 1 },
-            Point { x: 11, y: 1 },
-            Point { x: 11, y: 7 },
-            Point { x: 9, y: 7 },
-            Point { x: 9, y: 5 },
-            Point { x: 2, y: 5 },
-            Point { x: 2, y: 3 },
-            Point { x: 7, y: 3 },
-        ]
+        Self::parse_input(EXAMPLE.as_bytes()).unwrap()
     }
 
     fn example_output() -> Self::Output {
-        50
+        input::example_answer(ANSWERS, 1)
     }
 
     fn input_file() -> &'static str {
         "inputs/day09/input"
     }
 
-    fn parse_input<B>(reader: B) -> Result<Self::Input>
-    where
-        B: BufRead,
-    {
-        let mut points = Vec::new();
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        let text = parse::text(input)?;
+        let mut s = Scanner::new(text);
+        let points = s.lines(|s| {
+            let [x, y] = s.tuple(',', Scanner::unsigned)?;
+            Ok(Point::new(x, y))
+        })?;
+        s.finish()?;
 
-        for line in reader.lines() {
-            let line = line?;
-            let mut iter = line.trim().split(',');
+        Ok(points)
+    }
 
-            let x = iter.next().ok_or("invalid input")?.parse()?;
-            let y = iter.next().ok_or("invalid input")?.parse()?;
+    fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
+        Self::largest(&input)?.map_or(Ok(0), |rectangle| rectangle.size())
+    }
 
-            points.push(Point { x, y });
-        }
+    fn explain(&mut self, input: Self::Input) -> Result<Option<String>> {
+        Ok(Some(explain(Self::largest(&input)?)?))
+    }
 
-        Ok(points)
+    fn summary(input: &Self::Input) -> Option<String> {
+        Some(format!("{} vertices", input.len()))
     }
 
-    fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
-        let mut max = 0;
+    /// The polygon in green with its red corner tiles, and the largest rectangle inside it in
+    /// yellow.
+    #[cfg(feature = "std")]
+    fn render(input: &Self::Input) -> Option<Scene> {
+        let Bounds2 { max, .. } = Bounds2::from_points(input.iter().copied())?;
+        let mut scene = Scene::new(max.x + 2, max.y + 2);
 
-        for a in input.iter() {
-            for b in input.iter() {
-                let size = Rectangle::from_points(*a, *b).size();
+        for edge in Two::perimeter(input) {
+            scene.line(edge.a, edge.b, Color::GREEN);
+        }
 
-                if size > max {
-                    max = size;
-                }
-            }
+        for point in input {
+            scene.point(*point, Color::RED);
         }
 
-        Ok(max)
+        if let Ok(Some(Rectangle(bounds))) = Two::largest_inside(input) {
+            scene.rect(bounds, Color::YELLOW);
+        }
+
+        Some(scene)
     }
 }
 
 pub struct Two;
 
-impl Two {
-    fn intersects_any(rectangle: &Rectangle, perimeter: &[Edge]) -> bool {
-        perimeter.iter().any(|line| rectangle.intersects(line))
-    }
-
-    fn all_corners_in_perimeter(rectangle: &Rectangle, perimeter: &[Edge]) -> bool {
-        'outer: for corner in rectangle.corners() {
-            // use ray-casting to see if point is in the polygon drawn by `perimeter`
-            let mut collisions = 0;
-
-            for line in perimeter.iter() {
-                if line.includes(corner) {
-                    // corners on a perimeter line count as inside the polygon
-                    continue 'outer;
-                }
+/// The tiles of the polygon on a grid compressed to its corners' distinct coordinates. Even
+/// indices are the coordinates themselves and odd ones the gaps between them, so every
+/// compressed cell is either wholly inside the polygon or wholly outside it. A margin of gap
+/// cells surrounds the polygon.
+struct Compressed {
+    xs: Vec<u64>,
+    ys: Vec<u64>,
+    width: usize,
+    /// Outside cells in the rectangle from the origin up to, excluding, each cell.
+    outside: Vec<u32>,
+}
 
-                if line.horizontal() {
-                    continue;
+impl Compressed {
+    fn new(points: &[Point]) -> Self {
+        let mut xs: Vec<u64> = points.iter().map(|p| p.x).collect();
+        let mut ys: Vec<u64> = points.iter().map(|p| p.y).collect();
+        xs.sort_unstable();
+        xs.dedup();
+        ys.sort_unstable();
+        ys.dedup();
+
+        let (width, height) = (2 * xs.len() + 1, 2 * ys.len() + 1);
+        let mut compressed = Self {
+            xs,
+            ys,
+            width,
+            outside: Vec::new(),
+        };
+
+        let mut boundary = vec![false; width * height];
+
+        for edge in Two::perimeter(points) {
+            let [x1, x2] = [edge.a.x, edge.b.x].map(|x| compressed.column(x));
+            let [y1, y2] = [edge.a.y, edge.b.y].map(|y| compressed.row(y));
+
+            for y in y1.min(y2)..=y1.max(y2) {
+                for x in x1.min(x2)..=x1.max(x2) {
+                    boundary[y * width + x] = true;
                 }
+            }
+        }
 
-                // cast ray straight to the right, only considering vertical perimeter lines
-                if line.min_x() > corner.x && line.min_y() <= corner.y && line.max_y() > corner.y {
-                    collisions += 1;
+        // flood the outside from the margin, which is never part of the polygon
+        let mut outside = vec![false; width * height];
+        let mut stack = vec![0];
+        outside[0] = true;
+
+        while let Some(cell) = stack.pop() {
+            let (x, y) = (cell % width, cell / width);
+            let neighbours = [
+                (x > 0).then(|| cell - 1),
+                (x + 1 < width).then(|| cell + 1),
+                (y > 0).then(|| cell - width),
+                (y + 1 < height).then(|| cell + width),
+            ];
+
+            for next in neighbours.into_iter().flatten() {
+                if !boundary[next] && !outside[next] {
+                    outside[next] = true;
+                    stack.push(next);
                 }
             }
+        }
+
+        let stride = width + 1;
+        compressed.outside = vec![0; stride * (height + 1)];
 
-            // an even number of collisions in any direction means the point is outside the polygon
-            if collisions % 2 == 0 {
-                return false;
+        for y in 0..height {
+            for x in 0..width {
+                compressed.outside[(y + 1) * stride + x + 1] = u32::from(outside[y * width + x])
+                    + compressed.outside[y * stride + x + 1]
+                    + compressed.outside[(y + 1) * stride + x]
+                    - compressed.outside[y * stride + x];
             }
         }
 
-        true
+        compressed
     }
 
-    fn check_perimeter(rectangle: &Rectangle, perimeter: &[Edge]) -> bool {
-        !Self::intersects_any(rectangle, perimeter)
-            && Self::all_corners_in_perimeter(rectangle, perimeter)
+    fn column(&self, x: u64) -> usize {
+        2 * self.xs.partition_point(|c| *c < x) + 1
+    }
+
+    fn row(&self, y: u64) -> usize {
+        2 * self.ys.partition_point(|c| *c < y) + 1
+    }
+
+    fn inside(&self, rectangle: &Rectangle) -> bool {
+        let Bounds2 { min, max } = rectangle.0;
+        let (x1, x2) = (self.column(min.x), self.column(max.x) + 1);
+        let (y1, y2) = (self.row(min.y), self.row(max.y) + 1);
+        let stride = self.width + 1;
+
+        self.outside[y2 * stride + x2] + self.outside[y1 * stride + x1]
+            == self.outside[y1 * stride + x2] + self.outside[y2 * stride + x1]
+    }
+}
+
+impl Two {
+    /// The edges between consecutive points, closing the loop back to the first one.
+    fn perimeter(points: &[Point]) -> Vec<Edge> {
+        let mut perimeter = Vec::new();
+
+        for i in 0..points.len() {
+            let a = points[i];
+            let b = points[(i + 1) % points.len()];
+
+            debug_assert!(a.x == b.x || a.y == b.y);
+
+            perimeter.push(Edge { a, b });
+        }
+
+        perimeter
+    }
+
+    /// The largest rectangle with opposite corners on `points` that lies within the polygon.
+    fn largest_inside(points: &[Point]) -> Result<Option<Rectangle>> {
+        let compressed = Compressed::new(points);
+        let mut max: Option<Rectangle> = None;
+        let mut max_size = 0;
+
+        for (i, a) in points.iter().enumerate() {
+            for b in &points[i..] {
+                let rectangle = Rectangle::from_points(*a, *b);
+                let size = rectangle.size()?;
+
+                if size > max_size && compressed.inside(&rectangle) {
+                    max = Some(rectangle);
+                    max_size = size;
+                }
+            }
+        }
+
+        Ok(max)
     }
 }
 
@@ -224,50 +322,93 @@ impl Puzzle for Two {
     type Input = Vec<Point>;
     type Output = u64;
 
+    const LAYOUT: Layout = One::LAYOUT;
+    const BUDGET: Duration = Duration::from_millis(20);
+
     fn example_input() -> Self::Input {
         One::example_input()
     }
 
     fn example_output() -> Self::Output {
-        24
+        input::example_answer(ANSWERS, 2)
     }
 
     fn input_file() -> &'static str {
         One::input_file()
     }
 
-    fn parse_input<B>(reader: B) -> Result<Self::Input>
-    where
-        B: BufRead,
-    {
-        One::parse_input(reader)
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        One::parse_input(input)
     }
 
     fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
-        let mut perimeter = Vec::new();
+        Self::largest_inside(&input)?.map_or(Ok(0), |rectangle| rectangle.size())
+    }
 
-        for i in 0..input.len() - 1 {
-            let a = input[i];
-            let b = input[i + 1];
+    fn explain(&mut self, input: Self::Input) -> Result<Option<String>> {
+        Ok(Some(explain(Self::largest_inside(&input)?)?))
+    }
+}
 
-            debug_assert!(a.x == b.x || a.y == b.y);
+/// Reference implementation of [`Two`], checking every rectangle against every edge of the
+/// perimeter.
+pub struct NaiveTwo;
 
-            perimeter.push(Edge { a, b });
+impl NaiveTwo {
+    fn intersects_any(rectangle: &Rectangle, perimeter: &[Edge]) -> bool {
+        perimeter.iter().any(|line| rectangle.intersects(line))
+    }
+
+    /// Whether the rectangle's center lies within the polygon, using ray-casting on doubled
+    /// coordinates so the center of any rectangle falls on a whole number.
+    fn center_in_perimeter(rectangle: &Rectangle, perimeter: &[Edge]) -> bool {
+        let Bounds2 { min, max } = rectangle.0;
+        let center = Point::new(min.x + max.x, min.y + max.y);
+        let mut collisions = 0;
+
+        for line in perimeter.iter() {
+            let bounds = Bounds2::new(line.a + line.a, line.b + line.b);
+
+            if bounds.contains(center) {
+                // points on a perimeter line count as inside the polygon
+                return true;
+            }
+
+            // cast ray straight to the right, only considering vertical perimeter lines
+            if !line.horizontal()
+                && bounds.min.x > center.x
+                && bounds.min.y <= center.y
+                && bounds.max.y > center.y
+            {
+                collisions += 1;
+            }
         }
 
-        perimeter.push(Edge {
-            a: *input.last().unwrap(),
-            b: input[0],
-        });
+        // an even number of collisions means the point is outside the polygon
+        collisions % 2 == 1
+    }
+
+    /// No edge crossing the rectangle means its inside is wholly within the polygon or wholly
+    /// outside it, so checking its center suffices.
+    fn check_perimeter(rectangle: &Rectangle, perimeter: &[Edge]) -> bool {
+        !Self::intersects_any(rectangle, perimeter)
+            && Self::center_in_perimeter(rectangle, perimeter)
+    }
 
-        let mut max = 0;
+    /// The largest rectangle with opposite corners on `points` that lies within the polygon.
+    fn largest_inside(points: &[Point]) -> Result<Option<Rectangle>> {
+        let perimeter = Two::perimeter(points);
+        let mut max: Option<Rectangle> = None;
+        let mut max_size = 0;
 
-        for a in input.iter() {
-            for b in input.iter() {
+        for a in points.iter() {
+            for b in points.iter() {
                 let rectangle = Rectangle::from_points(*a, *b);
+                let size = rectangle.size()?;
 
-                if rectangle.size() > max && Self::check_perimeter(&rectangle, &perimeter) {
-                    max = rectangle.size();
+                if size > max_size && Self::check_perimeter(&rectangle, &perimeter) {
+                    max = Some(rectangle);
+                    max_size = size;
                 }
             }
         }
@@ -276,6 +417,98 @@ impl Puzzle for Two {
     }
 }
 
+impl Puzzle for NaiveTwo {
+    type Input = <Two as Puzzle>::Input;
+    type Output = <Two as Puzzle>::Output;
+
+    const LAYOUT: Layout = Two::LAYOUT;
+
+    fn example_input() -> Self::Input {
+        Two::example_input()
+    }
+
+    fn example_output() -> Self::Output {
+        Two::example_output()
+    }
+
+    fn input_file() -> &'static str {
+        Two::input_file()
+    }
+
+    fn parse_input(input: &[u8]) -> Result<Self::Input> {
+        Two::parse_input(input)
+    }
+
+    fn solve(&mut self, input: Self::Input) -> Result<Self::Output> {
+        Self::largest_inside(&input)?.map_or(Ok(0), |rectangle| rectangle.size())
+    }
+}
+
+impl Anonymizer for One {
+    /// Translates the polygon to a random spot near the origin and starts its outline at
+    /// another corner.
+    fn anonymize(text: &str, rng: &mut Rng) -> Result<String> {
+        let mut corners = Self::parse_input(text.as_bytes())?;
+        let min_x = corners.iter().map(|p| p.x).min().unwrap_or(0);
+        let min_y = corners.iter().map(|p| p.y).min().unwrap_or(0);
+        let offset = Point::new(rng.below(1000), rng.below(1000));
+
+        let start = rng.below(corners.len() as u64) as usize;
+        corners.rotate_left(start);
+
+        Ok(corners
+            .iter()
+            .map(|p| format!("{}\n", Point::new(p.x - min_x, p.y - min_y) + offset))
+            .collect())
+    }
+}
+
+impl Generator for One {
+    /// Generates a polygon out of `size / 4` adjacent columns. Every column spans the middle of
+    /// the coordinate range, so neighbouring columns always overlap and the outline is simple.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        const MID: u64 = 50_000;
+
+        let columns = (size / 4).max(1);
+
+        let mut xs = vec![rng.below(1000)];
+        let mut tops: Vec<u64> = Vec::new();
+        let mut bottoms: Vec<u64> = Vec::new();
+
+        for _ in 0..columns {
+            xs.push(xs.last().unwrap() + rng.range(1..=1000));
+
+            // consecutive columns with the same top or bottom would produce collinear edges
+            let mut top = rng.range(MID + 1..=2 * MID);
+            while tops.last() == Some(&top) {
+                top = rng.range(MID + 1..=2 * MID);
+            }
+
+            let mut bottom = rng.below(MID);
+            while bottoms.last() == Some(&bottom) {
+                bottom = rng.below(MID);
+            }
+
+            tops.push(top);
+            bottoms.push(bottom);
+        }
+
+        let mut vertices = Vec::new();
+
+        for (i, top) in tops.iter().enumerate() {
+            vertices.push((xs[i], *top));
+            vertices.push((xs[i + 1], *top));
+        }
+
+        for (i, bottom) in bottoms.iter().enumerate().rev() {
+            vertices.push((xs[i + 1], *bottom));
+            vertices.push((xs[i], *bottom));
+        }
+
+        vertices.iter().map(|(x, y)| format!("{x},{y}\n")).collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -291,4 +524,21 @@ mod tests {
         let mut two = Two;
         two.test_example()
     }
+
+    #[test]
+    fn naive() -> Result<()> {
+        let mut two = NaiveTwo;
+        two.test_example()
+    }
+
+    /// A rectangle with every corner on the outline but its inside outside the polygon, in the
+    /// notch under the middle column.
+    #[test]
+    fn notch() -> Result<()> {
+        let text = "0,10\n2,10\n2,8\n12,8\n12,9\n14,9\n14,0\n12,0\n12,5\n2,5\n2,1\n0,1\n";
+
+        assert_eq!(Two.solve(Two::parse_input(text.as_bytes())?)?, 44);
+        assert_eq!(NaiveTwo.solve(NaiveTwo::parse_input(text.as_bytes())?)?, 44);
+        Ok(())
+    }
 }
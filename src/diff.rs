@@ -0,0 +1,175 @@
+//! Differential testing of the implementations registered for the same day and part.
+
+use crate::Result;
+use crate::generate::Rng;
+use crate::input;
+use crate::registry::{Day, Implementation};
+use std::panic::{self, AssertUnwindSafe};
+
+/// Run `imp` on `text`, turning panics into errors so that shrinking may feed solvers inputs
+/// they were never meant to see.
+fn outcome(imp: &Implementation, text: &str) -> Option<String> {
+    match panic::catch_unwind(AssertUnwindSafe(|| (imp.solve)(text))) {
+        Ok(Ok(answer)) => Some(answer),
+        Ok(Err(_)) | Err(_) => None,
+    }
+}
+
+fn disagree(imps: &[&Implementation], text: &str) -> bool {
+    let mut outcomes = imps.iter().map(|imp| outcome(imp, text));
+    let first = outcomes.next().flatten();
+    outcomes.any(|other| other != first)
+}
+
+/// Greedily remove chunks of `items`, halving the chunk size down to single items, for as long
+/// as `fails` keeps holding.
+fn shrink_items(mut items: Vec<String>, fails: impl Fn(&[String]) -> bool) -> Vec<String> {
+    let mut chunk = (items.len() / 2).max(1);
+
+    loop {
+        let mut start = 0;
+        let mut removed = false;
+
+        while start < items.len() {
+            let end = (start + chunk).min(items.len());
+            let candidate = [&items[..start], &items[end..]].concat();
+
+            if fails(&candidate) {
+                items = candidate;
+                removed = true;
+            } else {
+                start = end;
+            }
+        }
+
+        if chunk > 1 {
+            chunk /= 2;
+        } else if !removed {
+            return items;
+        }
+    }
+}
+
+fn join_lines(lines: &[String]) -> String {
+    lines.iter().map(|line| format!("{line}\n")).collect()
+}
+
+/// Find a small input on which `fails` still holds, first by dropping whole lines and then by
+/// dropping comma-separated fields within the remaining lines.
+pub fn shrink(text: &str, fails: impl Fn(&str) -> bool) -> String {
+    let lines = text.lines().map(str::to_owned).collect();
+    let mut lines = shrink_items(lines, |lines| fails(&join_lines(lines)));
+
+    for i in 0..lines.len() {
+        let fields = lines[i].split(',').map(str::to_owned).collect();
+
+        let fields = shrink_items(fields, |fields| {
+            let mut candidate = lines.clone();
+            candidate[i] = fields.join(",");
+            fails(&join_lines(&candidate))
+        });
+
+        lines[i] = fields.join(",");
+    }
+
+    join_lines(&lines)
+}
+
+/// Number and size of the generated inputs every day is tested on in addition to its example
+/// and real input. Kept small so that naive implementations stay fast.
+const GENERATED_INPUTS: u64 = 16;
+const GENERATED_SIZE: usize = 12;
+
+fn inputs(day: &Day) -> Vec<(String, String)> {
+    let mut inputs = vec![("example input".to_owned(), day.example.to_owned())];
+
+    match input::read((day.input_file)()) {
+        Ok(text) => inputs.push(("real input".to_owned(), text)),
+        Err(e) => println!("day {}: skipping real input: {e}", day.number),
+    }
+
+    for seed in 0..GENERATED_INPUTS {
+        let text = (day.generate)(&mut Rng::new(seed), GENERATED_SIZE);
+        inputs.push((format!("generated input (seed {seed})"), text));
+    }
+
+    inputs
+}
+
+/// Compare every implementation of every part of `days` against each other, returning an error
+/// if any of them disagree.
+pub fn diff_days<'a>(days: impl IntoIterator<Item = &'a Day>) -> Result<()> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut disagreements = 0;
+
+    for day in days {
+        for part in 1..=2 {
+            let imps: Vec<&Implementation> = day.implementations(part).collect();
+
+            if imps.len() < 2 {
+                continue;
+            }
+
+            let inputs = inputs(day);
+            let mut agreed = 0;
+
+            for (label, text) in inputs.iter() {
+                if !disagree(&imps, text) {
+                    agreed += 1;
+                    continue;
+                }
+
+                disagreements += 1;
+                println!("day {} part {part}, {label}: disagreement", day.number);
+
+                let smallest = shrink(text, |text| disagree(&imps, text));
+
+                for imp in imps.iter() {
+                    let answer = outcome(imp, &smallest).unwrap_or_else(|| "failed".to_owned());
+                    println!("  {}: {answer}", imp.name);
+                }
+
+                println!("  smallest reproducing input:");
+
+                for line in smallest.lines() {
+                    println!("    {line}");
+                }
+            }
+
+            println!(
+                "day {} part {part}: {agreed}/{} inputs agree",
+                day.number,
+                inputs.len()
+            );
+        }
+    }
+
+    panic::set_hook(hook);
+
+    match disagreements {
+        0 => Ok(()),
+        n => Err(format!("{n} disagreement(s) found").into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrink_lines_and_fields() {
+        let text = "1,2,3\n4,5,6\n7,8,9\n";
+        let fails = |text: &str| text.contains('5') && text.contains('9');
+
+        assert_eq!(shrink(text, fails), "5\n9\n");
+    }
+
+    #[test]
+    fn shrink_only_while_failing() {
+        let text = "3-5\n10-14\n";
+
+        assert_eq!(shrink(text, |_| false), text);
+    }
+}
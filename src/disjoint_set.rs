@@ -0,0 +1,159 @@
+//! Union-find over the dense indices `0..n`, with path compression and union by size.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl DisjointSet {
+    /// Creates `n` singleton components.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            count: n,
+        }
+    }
+
+    /// Number of elements, not components.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Number of components.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Representative of the component containing `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut x = x;
+
+        while self.parent[x] != root {
+            x = core::mem::replace(&mut self.parent[x], root);
+        }
+
+        root
+    }
+
+    /// Merges the components containing `a` and `b`, returning false if they were already one.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+
+        if a == b {
+            return false;
+        }
+
+        let (big, small) = if self.size[a] >= self.size[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+
+        self.parent[small] = big;
+        self.size[big] += self.size[small];
+        self.count -= 1;
+
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Size of the component containing `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Sizes of all components, in no particular order.
+    pub fn sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len())
+            .filter(|&x| self.parent[x] == x)
+            .map(|root| self.size[root])
+    }
+
+    /// Members of every component, each sorted, ordered by their smallest member.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut index = vec![usize::MAX; self.len()];
+        let mut components: Vec<Vec<usize>> = Vec::with_capacity(self.count);
+
+        for x in 0..self.len() {
+            let root = self.find(x);
+
+            if index[root] == usize::MAX {
+                index[root] = components.len();
+                components.push(Vec::with_capacity(self.size[root]));
+            }
+
+            components[index[root]].push(x);
+        }
+
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_find() {
+        let mut set = DisjointSet::new(6);
+        assert_eq!(set.count(), 6);
+
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 3));
+        assert!(set.union(1, 3));
+        assert!(!set.union(0, 2));
+
+        assert_eq!(set.count(), 3);
+        assert!(set.same(0, 3));
+        assert!(!set.same(0, 4));
+        assert_eq!(set.size(2), 4);
+        assert_eq!(set.size(5), 1);
+    }
+
+    #[test]
+    fn components() {
+        let mut set = DisjointSet::new(5);
+        set.union(4, 1);
+        set.union(3, 0);
+
+        assert_eq!(set.components(), vec![vec![0, 3], vec![1, 4], vec![2]]);
+
+        let mut sizes: Vec<usize> = set.sizes().collect();
+        sizes.sort_unstable();
+        assert_eq!(sizes, vec![1, 2, 2]);
+    }
+
+    #[test]
+    fn long_chain() {
+        let n = 100_000;
+        let mut set = DisjointSet::new(n);
+
+        for x in 1..n {
+            set.union(x - 1, x);
+        }
+
+        assert_eq!(set.count(), 1);
+        assert_eq!(set.size(0), n);
+        assert!(set.same(0, n - 1));
+    }
+}
@@ -0,0 +1,252 @@
+//! A compact binary form of parsed inputs, so repeated runs can skip parsing. Every input type
+//! encodes itself by hand: integers are little-endian and lengths are `u64`s.
+
+use crate::Result;
+use crate::hash::fnv1a;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+/// Starts every stored input, so no other file is mistaken for one.
+const MAGIC: &[u8; 4] = b"aocp";
+
+/// Bump whenever an encoding changes, so inputs stored in the old format are rejected.
+pub const VERSION: u32 = 2;
+
+pub trait Encode {
+    fn encode(&self, out: &mut Vec<u8>);
+}
+
+/// The inverse of [`Encode`]. Like a parsed input may borrow from its text, a decoded one may
+/// borrow from its bytes.
+pub trait Decode<'a>: Sized {
+    fn decode(r: &mut Reader<'a>) -> Result<Self>;
+}
+
+/// Cursor over encoded bytes.
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    pub fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        if n > self.bytes.len() {
+            return Err("truncated encoding".into());
+        }
+
+        let (head, tail) = self.bytes.split_at(n);
+        self.bytes = tail;
+
+        Ok(head)
+    }
+
+    /// The length of a sequence. No element takes less than a byte, so a length beyond the
+    /// bytes left is corrupt, and refusing it avoids a huge allocation.
+    pub fn length(&mut self) -> Result<usize> {
+        let n = usize::decode(self)?;
+
+        if n > self.bytes.len() {
+            return Err("corrupt length in encoding".into());
+        }
+
+        Ok(n)
+    }
+
+    /// Requires every byte to have been decoded.
+    pub fn finish(self) -> Result<()> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err("trailing bytes in encoding".into())
+        }
+    }
+}
+
+/// Encodes `value` behind a header recording the format version, hashes of the solver and
+/// source text it was parsed with, and a checksum of the encoding itself.
+pub fn store<T: Encode + ?Sized>(value: &T, solver: u64, source: u64) -> Vec<u8> {
+    let mut payload = Vec::new();
+    value.encode(&mut payload);
+
+    let mut out = MAGIC.to_vec();
+    VERSION.encode(&mut out);
+    solver.encode(&mut out);
+    source.encode(&mut out);
+    fnv1a(&payload).encode(&mut out);
+    out.extend_from_slice(&payload);
+
+    out
+}
+
+/// Decodes a value written by [`store`], or `None` if it is corrupt or was stored by another
+/// version, for another solver or from another source. The checksum catches corruption that
+/// decoding can't, e.g. a changed byte in a borrowed `&[u8]` that its user expects to be a digit.
+pub fn load<'a, T: Decode<'a>>(bytes: &'a [u8], solver: u64, source: u64) -> Option<T> {
+    let mut r = Reader::new(bytes.strip_prefix(MAGIC)?);
+
+    let version = u32::decode(&mut r).ok()?;
+    let header = (u64::decode(&mut r).ok()?, u64::decode(&mut r).ok()?);
+    let checksum = u64::decode(&mut r).ok()?;
+
+    if version != VERSION || header != (solver, source) || checksum != fnv1a(r.bytes) {
+        return None;
+    }
+
+    let value = T::decode(&mut r).ok()?;
+    r.finish().ok()?;
+
+    Some(value)
+}
+
+macro_rules! int {
+    ($($t:ty),*) => {
+        $(
+            impl Encode for $t {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+            }
+
+            impl Decode<'_> for $t {
+                fn decode(r: &mut Reader<'_>) -> Result<Self> {
+                    Ok(Self::from_le_bytes(r.take(size_of::<Self>())?.try_into()?))
+                }
+            }
+        )*
+    };
+}
+
+int!(u8, u32, u64, i32);
+
+impl Encode for usize {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (*self as u64).encode(out);
+    }
+}
+
+impl Decode<'_> for usize {
+    fn decode(r: &mut Reader<'_>) -> Result<Self> {
+        Ok(u64::decode(r)?.try_into()?)
+    }
+}
+
+impl<A: Encode, B: Encode> Encode for (A, B) {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.0.encode(out);
+        self.1.encode(out);
+    }
+}
+
+impl<'a, A: Decode<'a>, B: Decode<'a>> Decode<'a> for (A, B) {
+    fn decode(r: &mut Reader<'a>) -> Result<Self> {
+        Ok((A::decode(r)?, B::decode(r)?))
+    }
+}
+
+impl<T: Encode> Encode for RangeInclusive<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.start().encode(out);
+        self.end().encode(out);
+    }
+}
+
+impl<'a, T: Decode<'a>> Decode<'a> for RangeInclusive<T> {
+    fn decode(r: &mut Reader<'a>) -> Result<Self> {
+        Ok(T::decode(r)?..=T::decode(r)?)
+    }
+}
+
+impl<T: Encode> Encode for [T] {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.len().encode(out);
+
+        for item in self {
+            item.encode(out);
+        }
+    }
+}
+
+impl<T: Encode> Encode for Vec<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.as_slice().encode(out);
+    }
+}
+
+impl<'a, T: Decode<'a>> Decode<'a> for Vec<T> {
+    fn decode(r: &mut Reader<'a>) -> Result<Self> {
+        (0..r.length()?).map(|_| T::decode(r)).collect()
+    }
+}
+
+impl<T: Encode> Encode for BTreeSet<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.len().encode(out);
+
+        for item in self {
+            item.encode(out);
+        }
+    }
+}
+
+impl<'a, T: Decode<'a> + Ord> Decode<'a> for BTreeSet<T> {
+    fn decode(r: &mut Reader<'a>) -> Result<Self> {
+        (0..r.length()?).map(|_| T::decode(r)).collect()
+    }
+}
+
+/// Decoded without copying, borrowing from the encoded bytes.
+impl<'a> Decode<'a> for &'a [u8] {
+    fn decode(r: &mut Reader<'a>) -> Result<Self> {
+        let n = r.length()?;
+        r.take(n)
+    }
+}
+
+impl<T: Encode + ?Sized> Encode for &T {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (**self).encode(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() -> Result<()> {
+        let value: (Vec<RangeInclusive<u64>>, Vec<&[u8]>) =
+            (vec![3..=5, 10..=14], vec![b"987", b"", b"12"]);
+
+        let mut bytes = Vec::new();
+        value.encode(&mut bytes);
+
+        let mut r = Reader::new(&bytes);
+        assert_eq!(
+            <(Vec<RangeInclusive<u64>>, Vec<&[u8]>)>::decode(&mut r)?,
+            value
+        );
+        r.finish()
+    }
+
+    #[test]
+    fn stale() {
+        let bytes = store(&vec![1u32, 2, 3], 7, 42);
+
+        assert_eq!(load::<Vec<u32>>(&bytes, 7, 42), Some(vec![1, 2, 3]));
+        assert_eq!(load::<Vec<u32>>(&bytes, 8, 42), None);
+        assert_eq!(load::<Vec<u32>>(&bytes, 7, 43), None);
+        assert_eq!(load::<Vec<u32>>(&bytes[..bytes.len() - 1], 7, 42), None);
+
+        let mut old = bytes.clone();
+        old[MAGIC.len()] ^= 0xff;
+        assert_eq!(load::<Vec<u32>>(&old, 7, 42), None);
+
+        let mut corrupt = bytes.clone();
+        *corrupt.last_mut().unwrap() ^= 0xff;
+        assert_eq!(load::<Vec<u32>>(&corrupt, 7, 42), None);
+    }
+}
@@ -0,0 +1,109 @@
+//! Seeded random puzzle inputs for stress testing and benchmarking.
+
+use alloc::string::String;
+use core::ops::RangeInclusive;
+
+/// SplitMix64, which is plenty for generating puzzle inputs and needs no dependencies.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, or 0 if `n` is 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        ((u128::from(self.next_u64()) * u128::from(n)) >> 64) as u64
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let span = range.end() - range.start();
+
+        match span.checked_add(1) {
+            Some(n) => range.start() + self.below(n),
+            None => self.next_u64(),
+        }
+    }
+
+    /// True with a probability of `numerator / denominator`.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    /// `value` moved by up to `spread` either way, but not below zero.
+    pub fn jitter(&mut self, value: u64, spread: u64) -> u64 {
+        let low = value.saturating_sub(spread);
+        let high = value.saturating_add(spread);
+
+        self.range(low..=high)
+    }
+
+    /// Fisher-Yates.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+pub trait Generator {
+    /// Produce a valid puzzle input. What `size` counts depends on the day, e.g. turns for
+    /// day 1 or the side length of the grid for day 4.
+    fn generate(rng: &mut Rng, size: usize) -> String;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::{Result, registry::DAYS};
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn deterministic() {
+        for day in DAYS {
+            let a = (day.generate)(&mut Rng::new(42), 10);
+            let b = (day.generate)(&mut Rng::new(42), 10);
+            assert_eq!(a, b, "day {}", day.number);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn generated_inputs_are_valid() -> Result<()> {
+        for day in DAYS {
+            for seed in 0..4 {
+                let text = (day.generate)(&mut Rng::new(seed), 10);
+
+                for imp in day.implementations {
+                    (imp.solve)(&text).map_err(|e| {
+                        format!("day {} part {} seed {seed}: {e}", day.number, imp.part)
+                    })?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn range_bounds() {
+        let mut rng = Rng::new(0);
+
+        for _ in 0..1000 {
+            assert!((3..=5).contains(&rng.range(3..=5)));
+        }
+
+        assert_eq!(rng.range(7..=7), 7);
+        rng.range(0..=u64::MAX);
+    }
+}
@@ -0,0 +1,408 @@
+//! Points and boxes in two and three dimensions.
+
+use crate::Result;
+use crate::encode::{Decode, Encode, Reader};
+use alloc::format;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Add, Mul, Sub};
+use core::str::FromStr;
+
+pub trait Coordinate:
+    Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    fn abs_diff(self, other: Self) -> Self;
+}
+
+macro_rules! unsigned_impl {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                fn abs_diff(self, other: Self) -> Self {
+                    <$t>::abs_diff(self, other)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! signed_impl {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                fn abs_diff(self, other: Self) -> Self {
+                    (self - other).abs()
+                }
+            }
+        )*
+    };
+}
+
+unsigned_impl!(u32, u64, usize);
+signed_impl!(i32, i64, isize);
+
+/// Parse exactly `N` comma-separated components.
+fn components<T, const N: usize>(s: &str) -> Result<[T; N]>
+where
+    T: FromStr,
+    T::Err: core::error::Error + 'static,
+{
+    let mut parsed = Vec::with_capacity(N);
+
+    for component in s.trim().split(',') {
+        parsed.push(component.trim().parse::<T>()?);
+    }
+
+    parsed
+        .try_into()
+        .map_err(|_| format!("expected {N} comma-separated components: {s}").into())
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Coordinate> Point2<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Component-wise distance.
+    pub fn abs_diff(self, other: Self) -> Self {
+        Self::new(self.x.abs_diff(other.x), self.y.abs_diff(other.y))
+    }
+
+    pub fn manhattan(self, other: Self) -> T {
+        let d = self.abs_diff(other);
+        d.x + d.y
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        let d = self.abs_diff(other);
+        d.x.max(d.y)
+    }
+
+    pub fn distance_squared(self, other: Self) -> T {
+        let d = self.abs_diff(other);
+        d.x * d.x + d.y * d.y
+    }
+
+    /// Component-wise minimum.
+    pub fn min(self, other: Self) -> Self {
+        Self::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    /// Component-wise maximum.
+    pub fn max(self, other: Self) -> Self {
+        Self::new(self.x.max(other.x), self.y.max(other.y))
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl<T: Encode> Encode for Point2<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.x.encode(out);
+        self.y.encode(out);
+    }
+}
+
+impl<'a, T: Decode<'a>> Decode<'a> for Point2<T> {
+    fn decode(r: &mut Reader<'a>) -> Result<Self> {
+        Ok(Self {
+            x: T::decode(r)?,
+            y: T::decode(r)?,
+        })
+    }
+}
+
+/// Parses `x,y`.
+impl<T> FromStr for Point2<T>
+where
+    T: FromStr,
+    T::Err: core::error::Error + 'static,
+{
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let [x, y] = components(s)?;
+        Ok(Self { x, y })
+    }
+}
+
+/// Formats as `x,y`, the way it is parsed.
+impl<T: fmt::Display> fmt::Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Coordinate> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Component-wise distance.
+    pub fn abs_diff(self, other: Self) -> Self {
+        Self::new(
+            self.x.abs_diff(other.x),
+            self.y.abs_diff(other.y),
+            self.z.abs_diff(other.z),
+        )
+    }
+
+    pub fn manhattan(self, other: Self) -> T {
+        let d = self.abs_diff(other);
+        d.x + d.y + d.z
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        let d = self.abs_diff(other);
+        d.x.max(d.y).max(d.z)
+    }
+
+    pub fn distance_squared(self, other: Self) -> T {
+        let d = self.abs_diff(other);
+        d.x * d.x + d.y * d.y + d.z * d.z
+    }
+
+    /// Component-wise minimum.
+    pub fn min(self, other: Self) -> Self {
+        Self::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    /// Component-wise maximum.
+    pub fn max(self, other: Self) -> Self {
+        Self::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl<T: Encode> Encode for Point3<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.x.encode(out);
+        self.y.encode(out);
+        self.z.encode(out);
+    }
+}
+
+impl<'a, T: Decode<'a>> Decode<'a> for Point3<T> {
+    fn decode(r: &mut Reader<'a>) -> Result<Self> {
+        Ok(Self {
+            x: T::decode(r)?,
+            y: T::decode(r)?,
+            z: T::decode(r)?,
+        })
+    }
+}
+
+/// Parses `x,y,z`.
+impl<T> FromStr for Point3<T>
+where
+    T: FromStr,
+    T::Err: core::error::Error + 'static,
+{
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let [x, y, z] = components(s)?;
+        Ok(Self { x, y, z })
+    }
+}
+
+/// Formats as `x,y,z`, the way it is parsed.
+impl<T: fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// Axis-aligned box with inclusive corners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds2<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+impl<T: Coordinate> Bounds2<T> {
+    /// The box spanned by two opposite corners.
+    pub fn new(a: Point2<T>, b: Point2<T>) -> Self {
+        Self {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    /// The smallest box containing all `points`, if there are any.
+    pub fn from_points(points: impl IntoIterator<Item = Point2<T>>) -> Option<Self> {
+        points
+            .into_iter()
+            .map(|pt| Self::new(pt, pt))
+            .reduce(|acc, e| Self::new(acc.min.min(e.min), acc.max.max(e.max)))
+    }
+
+    pub fn contains(&self, pt: Point2<T>) -> bool {
+        self.min.x <= pt.x && pt.x <= self.max.x && self.min.y <= pt.y && pt.y <= self.max.y
+    }
+
+    pub fn corners(&self) -> [Point2<T>; 4] {
+        [
+            self.min,
+            Point2::new(self.max.x, self.min.y),
+            self.max,
+            Point2::new(self.min.x, self.max.y),
+        ]
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y
+    }
+}
+
+/// Axis-aligned box with inclusive corners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds3<T> {
+    pub min: Point3<T>,
+    pub max: Point3<T>,
+}
+
+impl<T: Coordinate> Bounds3<T> {
+    /// The box spanned by two opposite corners.
+    pub fn new(a: Point3<T>, b: Point3<T>) -> Self {
+        Self {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    /// The smallest box containing all `points`, if there are any.
+    pub fn from_points(points: impl IntoIterator<Item = Point3<T>>) -> Option<Self> {
+        points
+            .into_iter()
+            .map(|pt| Self::new(pt, pt))
+            .reduce(|acc, e| Self::new(acc.min.min(e.min), acc.max.max(e.max)))
+    }
+
+    pub fn contains(&self, pt: Point3<T>) -> bool {
+        self.min.x <= pt.x
+            && pt.x <= self.max.x
+            && self.min.y <= pt.y
+            && pt.y <= self.max.y
+            && self.min.z <= pt.z
+            && pt.z <= self.max.z
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let a = Point2::new(1i64, 5);
+        let b = Point2::new(4, 1);
+
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.distance_squared(b), 25);
+        assert_eq!(a + b - a, b);
+
+        let a = Point3::new(162u64, 817, 812);
+        let b = Point3::new(425, 690, 689);
+
+        assert_eq!(a.manhattan(b), 263 + 127 + 123);
+        assert_eq!(a.chebyshev(b), 263);
+        assert_eq!(a.distance_squared(b), 263 * 263 + 127 * 127 + 123 * 123);
+    }
+
+    #[test]
+    fn parse() -> Result<()> {
+        assert_eq!("7,1".parse::<Point2<u64>>()?, Point2::new(7, 1));
+        assert_eq!(" 1, -2,3 ".parse::<Point3<i32>>()?, Point3::new(1, -2, 3));
+        assert!("1,2,3".parse::<Point2<u64>>().is_err());
+        assert!("1,2".parse::<Point3<u64>>().is_err());
+        assert!("1,x".parse::<Point2<u64>>().is_err());
+        assert_eq!(Point3::new(1, -2, 3).to_string(), "1,-2,3");
+        Ok(())
+    }
+
+    #[test]
+    fn bounds() {
+        let points = [Point2::new(7u64, 1), Point2::new(11, 7), Point2::new(2, 5)];
+        let bounds = Bounds2::from_points(points).unwrap();
+
+        assert_eq!(bounds, Bounds2::new(Point2::new(11, 1), Point2::new(2, 7)));
+        assert_eq!((bounds.width(), bounds.height()), (9, 6));
+        assert!(bounds.contains(Point2::new(2, 1)));
+        assert!(!bounds.contains(Point2::new(12, 1)));
+        assert_eq!(Bounds2::<u64>::from_points([]), None);
+
+        let bounds = Bounds3::from_points([Point3::new(1u64, 2, 3), Point3::new(3, 2, 1)]).unwrap();
+        assert!(bounds.contains(Point3::new(2, 2, 2)));
+        assert!(!bounds.contains(Point3::new(2, 3, 2)));
+    }
+}
@@ -0,0 +1,272 @@
+//! Directed graphs over dense node ids, with names interned separately.
+
+use crate::Result;
+use crate::checked;
+use alloc::borrow::ToOwned;
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// Maps names to dense ids in order of first appearance.
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    ids: BTreeMap<String, usize>,
+    names: Vec<String>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// The id of `name`, assigning the next one if it is new.
+    pub fn intern(&mut self, name: &str) -> usize {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+
+        let id = self.names.len();
+        self.ids.insert(name.to_owned(), id);
+        self.names.push(name.to_owned());
+
+        id
+    }
+
+    pub fn get(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+}
+
+/// Adjacency lists; parallel edges are kept, so they count separately as paths.
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    /// Creates `n` nodes without edges.
+    pub fn new(n: usize) -> Self {
+        Self {
+            adjacency: vec![Vec::new(); n],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adjacency.is_empty()
+    }
+
+    pub fn add_node(&mut self) -> usize {
+        self.adjacency.push(Vec::new());
+        self.adjacency.len() - 1
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        self.adjacency[from].push(to);
+    }
+
+    pub fn neighbors(&self, node: usize) -> &[usize] {
+        &self.adjacency[node]
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.adjacency
+            .iter()
+            .enumerate()
+            .flat_map(|(from, to)| to.iter().map(move |to| (from, *to)))
+    }
+
+    /// Number of edges on the shortest path from `start` to every node, if it is reachable.
+    pub fn bfs(&self, start: usize) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.len()];
+        let mut queue = VecDeque::from([start]);
+        distances[start] = Some(0);
+
+        while let Some(node) = queue.pop_front() {
+            let distance = distances[node].map(|d| d + 1);
+
+            for &next in self.neighbors(node) {
+                if distances[next].is_none() {
+                    distances[next] = distance;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Nodes reachable from `start` in depth-first preorder.
+    pub fn dfs(&self, start: usize) -> Vec<usize> {
+        let mut seen = vec![false; self.len()];
+        let mut stack = vec![start];
+        let mut order = Vec::new();
+
+        while let Some(node) = stack.pop() {
+            if core::mem::replace(&mut seen[node], true) {
+                continue;
+            }
+
+            order.push(node);
+            stack.extend(
+                self.neighbors(node)
+                    .iter()
+                    .rev()
+                    .filter(|next| !seen[**next]),
+            );
+        }
+
+        order
+    }
+
+    /// Kahn's algorithm; `None` if the graph has a cycle.
+    pub fn topological_order(&self) -> Option<Vec<usize>> {
+        let mut in_degree = vec![0; self.len()];
+
+        for (_, to) in self.edges() {
+            in_degree[to] += 1;
+        }
+
+        let mut queue: VecDeque<usize> = (0..self.len()).filter(|n| in_degree[*n] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+
+            for &next in self.neighbors(node) {
+                in_degree[next] -= 1;
+
+                if in_degree[next] == 0 {
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        (order.len() == self.len()).then_some(order)
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        self.topological_order().is_none()
+    }
+
+    /// Number of distinct paths from `from` to `to`, memoized per node in reverse topological
+    /// order. Errors on cycles, where the count could be infinite.
+    pub fn count_paths(&self, from: usize, to: usize) -> Result<u64> {
+        let order = self.topological_order().ok_or("graph has a cycle")?;
+        let mut paths = vec![0u64; self.len()];
+        paths[to] = 1;
+
+        for &node in order.iter().rev().filter(|node| **node != to) {
+            for &next in self.neighbors(node) {
+                paths[node] = checked::add("graph path count", paths[node], paths[next])?;
+            }
+        }
+
+        Ok(paths[from])
+    }
+
+    /// Graphviz rendering, naming nodes with `label`.
+    pub fn to_dot(&self, label: impl Fn(usize) -> String) -> String {
+        let mut out = String::from("digraph {\n");
+
+        for node in 0..self.len() {
+            writeln!(out, "    {node} [label={:?}];", label(node)).unwrap();
+        }
+
+        for (from, to) in self.edges() {
+            writeln!(out, "    {from} -> {to};").unwrap();
+        }
+
+        out.push_str("}\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &str)]) -> (Graph, Interner) {
+        let mut names = Interner::new();
+        let mut graph = Graph::default();
+
+        for (from, to) in edges {
+            let (from, to) = (names.intern(from), names.intern(to));
+
+            while graph.len() < names.len() {
+                graph.add_node();
+            }
+
+            graph.add_edge(from, to);
+        }
+
+        (graph, names)
+    }
+
+    #[test]
+    fn traversal() {
+        let (graph, names) = graph(&[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d"), ("d", "e")]);
+        let id = |name| names.get(name).unwrap();
+
+        assert_eq!(names.name(id("d")), "d");
+        assert_eq!(graph.bfs(id("a"))[id("e")], Some(3));
+        assert_eq!(graph.bfs(id("e"))[id("a")], None);
+        assert_eq!(
+            graph.dfs(id("a")),
+            ["a", "b", "d", "e", "c"].map(id).to_vec()
+        );
+    }
+
+    #[test]
+    fn paths() -> Result<()> {
+        let (graph, names) = graph(&[
+            ("you", "x"),
+            ("you", "y"),
+            ("x", "out"),
+            ("y", "out"),
+            ("y", "z"),
+            ("z", "out"),
+            ("z", "out"),
+        ]);
+        let id = |name| names.get(name).unwrap();
+
+        assert_eq!(graph.count_paths(id("you"), id("out"))?, 4);
+        assert_eq!(graph.count_paths(id("out"), id("you"))?, 0);
+
+        let order = graph.topological_order().unwrap();
+        assert_eq!(order.first(), Some(&id("you")));
+        assert_eq!(order.last(), Some(&id("out")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn cycles() {
+        let (graph, names) = graph(&[("a", "b"), ("b", "c"), ("c", "a")]);
+
+        assert!(graph.has_cycle());
+        assert!(graph.count_paths(0, 2).is_err());
+        assert_eq!(
+            graph.to_dot(|id| names.name(id).to_owned()),
+            "digraph {\n    0 [label=\"a\"];\n    1 [label=\"b\"];\n    2 [label=\"c\"];\n    \
+             0 -> 1;\n    1 -> 2;\n    2 -> 0;\n}\n"
+        );
+    }
+}
@@ -0,0 +1,273 @@
+//! Dense, row-major grids for the puzzles drawn in characters.
+
+use crate::encode::{Decode, Encode, Reader};
+use crate::{Result, parse};
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Index, IndexMut};
+
+const OFFSETS4: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+const OFFSETS8: [(isize, isize); 8] = [
+    (-1, 0),  // left
+    (1, 0),   // right
+    (0, -1),  // above
+    (0, 1),   // below
+    (-1, -1), // top left
+    (1, -1),  // top right
+    (-1, 1),  // bottom left
+    (1, 1),   // bottom right
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parse one row per line, mapping every character with `f`. Rows of differing width are
+    /// an error.
+    pub fn parse<F>(input: &[u8], mut f: F) -> Result<Self>
+    where
+        F: FnMut(char) -> Result<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for line in parse::text(input)?.lines() {
+            let before = cells.len();
+
+            for c in line.chars() {
+                cells.push(f(c)?);
+            }
+
+            let row_width = cells.len() - before;
+
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(format!(
+                        "ragged grid: row {height} has width {row_width}, expected {width}"
+                    )
+                    .into());
+                }
+                Some(_) => (),
+            }
+
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    fn offsets(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let x = x.checked_add_signed(*dx)?;
+            let y = y.checked_add_signed(*dy)?;
+            (x < width && y < height).then_some((x, y))
+        })
+    }
+
+    /// Positions of the horizontally and vertically adjacent cells that are within bounds.
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(x, y, &OFFSETS4)
+    }
+
+    /// Like [`Grid::neighbors4`], but including diagonally adjacent cells.
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(x, y, &OFFSETS8)
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) out of bounds"))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) out of bounds"))
+    }
+}
+
+impl<T: Encode> Encode for Grid<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.width.encode(out);
+        self.height.encode(out);
+        self.cells.encode(out);
+    }
+}
+
+impl<'a, T: Decode<'a>> Decode<'a> for Grid<T> {
+    fn decode(r: &mut Reader<'a>) -> Result<Self> {
+        let width = usize::decode(r)?;
+        let height = usize::decode(r)?;
+        let cells = Vec::decode(r)?;
+
+        if width.checked_mul(height) != Some(cells.len()) {
+            return Err("corrupt grid encoding".into());
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "ab\ncd\nef\n";
+
+    fn grid() -> Grid<char> {
+        Grid::parse(TEXT.as_bytes(), Ok).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let grid = grid();
+
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.to_string(), TEXT);
+    }
+
+    #[test]
+    fn ragged() {
+        let err = Grid::parse("ab\nc\n".as_bytes(), Ok).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ragged grid: row 1 has width 1, expected 2"
+        );
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = grid();
+
+        let mut corner: Vec<_> = grid.neighbors8(0, 0).collect();
+        corner.sort();
+        assert_eq!(corner, [(0, 1), (1, 0), (1, 1)]);
+
+        let mut edge: Vec<_> = grid.neighbors4(1, 1).collect();
+        edge.sort();
+        assert_eq!(edge, [(0, 1), (1, 0), (1, 2)]);
+
+        assert_eq!(grid.neighbors8(0, 1).count(), 5);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = grid();
+
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            [['a', 'b'], ['c', 'd'], ['e', 'f']]
+        );
+
+        let columns: Vec<String> = grid.columns().map(|column| column.collect()).collect();
+        assert_eq!(columns, ["ace", "bdf"]);
+
+        assert_eq!(grid.positions().nth(3), Some((1, 1)));
+    }
+}
@@ -0,0 +1,271 @@
+//! Pull example inputs and answers out of a locally saved puzzle page.
+//!
+//! Examples end up in `examples/dayNN/`: the code blocks as `example`, `example-2`, ... and the
+//! emphasized answer of each part as `<part> <answer>` lines in `answers`. Every day compiles
+//! in its `example` and `answers` for its example tests. Importing again adds what the page
+//! gained, e.g. part two's answer, but replaces nothing that changed without `--force`.
+
+use crate::Result;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Decode the entities found in puzzle pages, i.e. the named XML ones and numeric references.
+pub fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => '\u{a0}',
+                entity => {
+                    let code = match entity.strip_prefix("#x").or(entity.strip_prefix("#X")) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => entity.strip_prefix('#')?.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+pub fn strip_tags(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut in_tag = false;
+
+    for c in s.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => out.push(c),
+            _ => (),
+        }
+    }
+
+    out
+}
+
+fn text(html: &str) -> String {
+    decode_entities(&strip_tags(html))
+}
+
+/// All substrings of `s` between `open` and the next `close`.
+fn between<'a>(s: &'a str, open: &'a str, close: &'a str) -> impl Iterator<Item = &'a str> {
+    let mut rest = s;
+
+    std::iter::from_fn(move || {
+        let start = rest.find(open)? + open.len();
+        let len = rest[start..].find(close)?;
+        let found = &rest[start..start + len];
+        rest = &rest[start + len + close.len()..];
+        Some(found)
+    })
+}
+
+/// The contents of every `<pre><code>` block, which is how examples are presented.
+pub fn code_blocks(html: &str) -> Vec<String> {
+    between(html, "<pre><code>", "</code></pre>")
+        .map(text)
+        .collect()
+}
+
+/// The answer to the example of each part. Every part is an `<article>`, and the example's
+/// answer is the last emphasized code in it; intermediate results come before it.
+pub fn answers(html: &str) -> Vec<String> {
+    between(html, "<article class=\"day-desc\">", "</article>")
+        .filter_map(|article| between(article, "<code><em>", "</em></code>").last())
+        .map(text)
+        .collect()
+}
+
+pub fn examples_dir(day: u32) -> PathBuf {
+    Path::new("examples").join(format!("day{day:02}"))
+}
+
+/// Merges the answers found on a page into an existing `answers` file, so importing a page
+/// saved before part two was unlocked keeps a part two answer imported earlier. Fails if an
+/// answer differs from the one already there, unless `force` is given.
+fn merge_answers(existing: &str, found: &[String], force: bool) -> Result<String> {
+    let mut answers: BTreeMap<u8, &str> = existing
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .map(|(part, answer)| Ok((part.parse()?, answer)))
+        .collect::<Result<_>>()?;
+
+    for (part, answer) in (1..).zip(found) {
+        match answers.insert(part, answer) {
+            Some(old) if old != answer && !force => {
+                return Err(format!(
+                    "part {part} answer {answer} differs from {old}; pass --force to replace it"
+                )
+                .into());
+            }
+            _ => (),
+        }
+    }
+
+    Ok(answers
+        .iter()
+        .map(|(part, answer)| format!("{part} {answer}\n"))
+        .collect())
+}
+
+pub fn import_examples(day: u32, page: &Path, force: bool) -> Result<()> {
+    let html = std::fs::read_to_string(page)?;
+    import_into(&examples_dir(day), &html, force)
+}
+
+/// Writes the examples and answers found in `html` into `dir`. Checks every file first, so a
+/// refused import writes nothing.
+fn import_into(dir: &Path, html: &str, force: bool) -> Result<()> {
+    let blocks = code_blocks(html);
+
+    if blocks.is_empty() {
+        return Err("no examples found in the page".into());
+    }
+
+    let mut files: Vec<(PathBuf, String)> = blocks
+        .into_iter()
+        .enumerate()
+        .map(|(i, block)| match i {
+            0 => (dir.join("example"), block),
+            i => (dir.join(format!("example-{}", i + 1)), block),
+        })
+        .collect();
+
+    for (path, block) in &files {
+        let existing = std::fs::read_to_string(path).ok();
+
+        if existing.is_some_and(|existing| existing != *block) && !force {
+            return Err(format!("{} differs; pass --force to replace it", path.display()).into());
+        }
+    }
+
+    let found = answers(html);
+
+    if !found.is_empty() {
+        let path = dir.join("answers");
+        let existing = std::fs::read_to_string(&path).unwrap_or_default();
+        files.push((path, merge_answers(&existing, &found, force)?));
+    }
+
+    std::fs::create_dir_all(dir)?;
+
+    for (path, contents) in files {
+        if std::fs::read_to_string(&path).ok().as_ref() == Some(&contents) {
+            println!("{} is unchanged", path.display());
+        } else {
+            std::fs::write(&path, contents)?;
+            println!("wrote {}", path.display());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 5: Cafeteria ---</h2>
+<p>For example:</p>
+<pre><code>3-5
+10-14
+
+1
+<em>5</em>
+</code></pre>
+<p>Ingredient ID <code>5</code> is <em>fresh</em>, so <code><em>1</em></code> is fresh so far,
+and in total <code><em>3</em></code> of the available ingredient IDs are fresh.</p>
+</article>
+<p>Your puzzle answer was <code>601</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>a &lt; b &amp;&amp; c &#62; d &#x41;</code></pre>
+<p>Here, <code><em>14</em></code> IDs are fresh.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn blocks() {
+        assert_eq!(
+            code_blocks(PAGE),
+            ["3-5\n10-14\n\n1\n5\n", "a < b && c > d A"]
+        );
+    }
+
+    #[test]
+    fn part_answers() {
+        assert_eq!(answers(PAGE), ["3", "14"]);
+    }
+
+    #[test]
+    fn entities() {
+        assert_eq!(
+            decode_entities("&amp;lt; &bogus; & &#9731;"),
+            "&lt; &bogus; & \u{2603}"
+        );
+    }
+
+    #[test]
+    fn merge_keeps_other_parts() -> Result<()> {
+        let merged = merge_answers("1 3\n2 14\n", &["3".to_owned()], false)?;
+        assert_eq!(merged, "1 3\n2 14\n");
+
+        let merged = merge_answers("1 3\n", &["3".to_owned(), "14".to_owned()], false)?;
+        assert_eq!(merged, "1 3\n2 14\n");
+        Ok(())
+    }
+
+    #[test]
+    fn merge_conflict() -> Result<()> {
+        assert!(merge_answers("1 4\n", &["3".to_owned()], false).is_err());
+        assert_eq!(merge_answers("1 4\n", &["3".to_owned()], true)?, "1 3\n");
+        Ok(())
+    }
+
+    #[test]
+    fn keeps_changed_example() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("aoc2025-import-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(dir.join("example"), "edited\n")?;
+
+        let refused = import_into(&dir, PAGE, false);
+        let kept = std::fs::read_to_string(dir.join("example"))?;
+        let answers_written = dir.join("answers").exists();
+
+        import_into(&dir, PAGE, true)?;
+        let forced = std::fs::read_to_string(dir.join("example"))?;
+        std::fs::remove_dir_all(&dir)?;
+
+        assert!(refused.is_err());
+        assert_eq!(kept, "edited\n");
+        assert!(!answers_written);
+        assert_eq!(forced, code_blocks(PAGE)[0]);
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+//! Reading raw puzzle input and normalizing it before it reaches a parser.
+
+#[cfg(feature = "std")]
+use crate::Result;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+#[cfg(feature = "embed-inputs")]
+mod embedded {
+    include!(concat!(env!("OUT_DIR"), "/inputs.rs"));
+}
+
+/// Reads the input at `path`, preferring the copy compiled in with the `embed-inputs` feature.
+/// Inputs missing at build time are read from disk instead.
+#[cfg(feature = "std")]
+pub fn read(path: &str) -> Result<String> {
+    #[cfg(feature = "embed-inputs")]
+    if let Some((_, text)) = embedded::INPUTS
+        .iter()
+        .find(|(embedded, _)| *embedded == path)
+    {
+        return Ok((*text).to_owned());
+    }
+
+    std::fs::read_to_string(path).map_err(|e| {
+        if cfg!(feature = "embed-inputs") {
+            format!("{path} was not embedded at build time and can't be read: {e}").into()
+        } else {
+            format!("{path}: {e}").into()
+        }
+    })
+}
+
+/// Whether [`read`] takes the input at `path` from the binary rather than from disk.
+#[cfg(feature = "std")]
+pub fn is_embedded(path: &str) -> bool {
+    #[cfg(feature = "embed-inputs")]
+    return embedded::INPUTS
+        .iter()
+        .any(|(embedded, _)| *embedded == path);
+
+    #[cfg(not(feature = "embed-inputs"))]
+    {
+        let _ = path;
+        false
+    }
+}
+
+/// How a day's parser treats whitespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// Trailing whitespace carries no meaning and is stripped from every line.
+    Lines,
+    /// Columns are significant, so whitespace is kept and ragged lines are right-padded with
+    /// spaces to the width of the longest line.
+    Grid,
+}
+
+/// Strip a UTF-8 BOM, convert CRLF line endings to LF and drop trailing blank lines, then
+/// apply the whitespace rules of `layout`. Every line of the result ends in a newline.
+pub fn normalize(text: &str, layout: Layout) -> String {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+
+    let mut lines: Vec<&str> = text
+        .split('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .map(|line| match layout {
+            Layout::Lines => line.trim_end(),
+            Layout::Grid => line,
+        })
+        .collect();
+
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+
+    let width = match layout {
+        Layout::Lines => 0,
+        Layout::Grid => lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0),
+    };
+
+    let mut out = String::with_capacity(text.len() + lines.len());
+
+    for line in lines {
+        out.push_str(line);
+
+        for _ in line.chars().count()..width {
+            out.push(' ');
+        }
+
+        out.push('\n');
+    }
+
+    out
+}
+
+/// The answer to `part` in an `answers` file written by the `import-examples` command. Panics
+/// if it is missing or malformed, like a mistyped example would fail its test.
+pub fn example_answer<T: FromStr>(answers: &str, part: u8) -> T {
+    answers
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .find(|(n, _)| n.parse() == Ok(part))
+        .and_then(|(_, answer)| answer.parse().ok())
+        .unwrap_or_else(|| panic!("no valid answer to part {part} among the example answers"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bom_and_crlf() {
+        let text = "\u{feff}L68 \r\nR48\r\n\r\n";
+        assert_eq!(normalize(text, Layout::Lines), "L68\nR48\n");
+    }
+
+    #[test]
+    fn ragged_grid() {
+        let text = "..S..\r\n.^\r\n^.^.\r\n";
+        assert_eq!(normalize(text, Layout::Grid), "..S..\n.^   \n^.^. \n");
+    }
+
+    #[test]
+    fn keeps_inner_blank_lines() {
+        let text = "3-5\n\n1\n";
+        assert_eq!(normalize(text, Layout::Lines), text);
+    }
+}
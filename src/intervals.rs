@@ -0,0 +1,304 @@
+//! Sets of integers stored as sorted, disjoint inclusive ranges.
+
+use crate::Result;
+use crate::encode::{Decode, Encode, Reader};
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::RangeInclusive;
+
+pub trait Bound: Copy + Ord {
+    fn succ(self) -> Option<Self>;
+    fn pred(self) -> Option<Self>;
+
+    /// Number of values in `start..=end`, which does not fit `Self` for the full range.
+    fn span(start: Self, end: Self) -> u128;
+}
+
+macro_rules! bound_impl {
+    ($($t:ty),*) => {
+        $(
+            impl Bound for $t {
+                fn succ(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn pred(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn span(start: Self, end: Self) -> u128 {
+                    (end as i128 - start as i128) as u128 + 1
+                }
+            }
+        )*
+    };
+}
+
+bound_impl!(i32, i64, u32, u64, usize);
+
+/// Invariant: the ranges are sorted, non-empty and neither overlap nor touch, so every set has
+/// exactly one representation.
+#[derive(Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T: Bound> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Bound> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// Number of disjoint ranges.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of values in the set.
+    pub fn cardinality(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|(start, end)| T::span(*start, *end))
+            .sum()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.ranges.partition_point(|(_, end)| *end < value);
+        self.ranges
+            .get(idx)
+            .is_some_and(|(start, _)| *start <= value)
+    }
+
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+
+        if start > end {
+            return;
+        }
+
+        // ranges entirely before `start` without touching it stay, as do those after `end`
+        let lo = self
+            .ranges
+            .partition_point(|(_, e)| e.succ().is_some_and(|next| next < start));
+        let hi = self.ranges.partition_point(|(s, _)| match end.succ() {
+            Some(next) => *s <= next,
+            None => true,
+        });
+
+        if lo < hi {
+            start = start.min(self.ranges[lo].0);
+            end = end.max(self.ranges[hi - 1].1);
+        }
+
+        self.ranges.splice(lo..hi, [(start, end)]);
+    }
+
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+
+        if start > end {
+            return;
+        }
+
+        let lo = self.ranges.partition_point(|(_, e)| *e < start);
+        let hi = self.ranges.partition_point(|(s, _)| *s <= end);
+
+        if lo >= hi {
+            return;
+        }
+
+        let mut remainder = Vec::with_capacity(2);
+
+        if self.ranges[lo].0 < start {
+            // `start` can't be the minimum if something lies below it
+            remainder.push((self.ranges[lo].0, start.pred().unwrap()));
+        }
+
+        if self.ranges[hi - 1].1 > end {
+            remainder.push((end.succ().unwrap(), self.ranges[hi - 1].1));
+        }
+
+        self.ranges.splice(lo..hi, remainder);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> {
+        self.ranges.iter().map(|(start, end)| *start..=*end)
+    }
+
+    /// The ranges of values between the ranges of the set.
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> {
+        // ranges never touch, so neither bound can overflow
+        self.ranges
+            .windows(2)
+            .map(|pair| pair[0].1.succ().unwrap()..=pair[1].0.pred().unwrap())
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut out = self.clone();
+        out.extend(other.iter());
+        out
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a_start, a_end) = self.ranges[i];
+            let (b_start, b_end) = other.ranges[j];
+
+            let start = a_start.max(b_start);
+            let end = a_end.min(b_end);
+
+            if start <= end {
+                ranges.push((start, end));
+            }
+
+            if a_end < b_end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut out = self.clone();
+
+        for range in other.iter() {
+            out.remove(range);
+        }
+
+        out
+    }
+}
+
+impl<T: Bound> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Bound> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Bound + Encode> Encode for IntervalSet<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.ranges.encode(out);
+    }
+}
+
+/// Decoded through [`IntervalSet::insert`], so a corrupt encoding can't break the invariants.
+impl<'a, T: Bound + Decode<'a>> Decode<'a> for IntervalSet<T> {
+    fn decode(r: &mut Reader<'a>) -> Result<Self> {
+        let ranges: Vec<(T, T)> = Vec::decode(r)?;
+
+        Ok(ranges.into_iter().map(|(start, end)| start..=end).collect())
+    }
+}
+
+impl<T: Bound + fmt::Debug> fmt::Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[RangeInclusive<u64>]) -> IntervalSet<u64> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn insert_merges() {
+        let set = set(&[3..=5, 10..=14, 16..=20, 12..=18]);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [3..=5, 10..=20]);
+        assert_eq!(set.cardinality(), 14);
+
+        // touching ranges merge too
+        let set = self::set(&[1..=2, 5..=6, 3..=4]);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [1..=6]);
+
+        #[allow(clippy::reversed_empty_ranges)]
+        let set = self::set(&[5..=4]);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn remove_splits() {
+        let mut set = set(&[0..=10, 20..=30]);
+        set.remove(5..=22);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0..=4, 23..=30]);
+
+        set.remove(0..=0);
+        set.remove(30..=40);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [1..=4, 23..=29]);
+    }
+
+    #[test]
+    fn contains() {
+        let set = set(&[3..=5, 10..=20]);
+
+        for (value, expected) in [
+            (1, false),
+            (3, true),
+            (5, true),
+            (8, false),
+            (20, true),
+            (21, false),
+        ] {
+            assert_eq!(set.contains(value), expected, "{value}");
+        }
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[0..=10, 20..=30]);
+        let b = set(&[5..=25]);
+
+        assert_eq!(a.union(&b), set(&[0..=30]));
+        assert_eq!(a.intersection(&b), set(&[5..=10, 20..=25]));
+        assert_eq!(a.difference(&b), set(&[0..=4, 26..=30]));
+        assert_eq!(b.difference(&a), set(&[11..=19]));
+    }
+
+    #[test]
+    fn gaps() {
+        let set = set(&[0..=10, 20..=30, 32..=40]);
+        assert_eq!(set.gaps().collect::<Vec<_>>(), [11..=19, 31..=31]);
+    }
+
+    #[test]
+    fn extremes() {
+        let mut set = set(&[0..=u64::MAX]);
+        assert_eq!(set.cardinality(), u128::from(u64::MAX) + 1);
+
+        set.remove(1..=u64::MAX - 1);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0..=0, u64::MAX..=u64::MAX]);
+        set.insert(1..=u64::MAX - 1);
+        assert_eq!(set.len(), 1);
+
+        let set: IntervalSet<i32> = [i32::MIN..=-1, 0..=i32::MAX].into_iter().collect();
+        assert_eq!(set.cardinality(), 1 << 32);
+    }
+}
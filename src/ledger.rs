@@ -0,0 +1,233 @@
+//! Record of answers submitted for a day and part, and what the site said about them.
+//!
+//! Ledgers are plain text files next to the input, one attempt per line, e.g. `1234 too-high`.
+
+use crate::Result;
+use std::cmp::Ordering;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feedback {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl FromStr for Feedback {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "correct" => Ok(Self::Correct),
+            "wrong" => Ok(Self::Wrong),
+            "too-high" | "high" => Ok(Self::TooHigh),
+            "too-low" | "low" => Ok(Self::TooLow),
+            _ => Err(format!("not a feedback: {s}").into()),
+        }
+    }
+}
+
+impl fmt::Display for Feedback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::TooHigh => write!(f, "too-high"),
+            Self::TooLow => write!(f, "too-low"),
+        }
+    }
+}
+
+/// What the ledger knows about a freshly computed answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// The answer was submitted before and rejected.
+    KnownWrong(Feedback),
+    /// Differs from the answer known to be correct.
+    NotCorrect(String),
+    /// At least the given answer, which was too high.
+    TooHigh(String),
+    /// At most the given answer, which was too low.
+    TooLow(String),
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::KnownWrong(feedback) => write!(f, "warning: already submitted, was {feedback}"),
+            Self::NotCorrect(correct) => write!(f, "warning: the correct answer is {correct}"),
+            Self::TooHigh(bound) => write!(f, "warning: too high, {bound} already was"),
+            Self::TooLow(bound) => write!(f, "warning: too low, {bound} already was"),
+        }
+    }
+}
+
+/// Order answers numerically where both are numbers, which all of them are so far.
+fn compare(a: &str, b: &str) -> Option<Ordering> {
+    Some(a.parse::<i128>().ok()?.cmp(&b.parse::<i128>().ok()?))
+}
+
+pub struct Ledger {
+    path: PathBuf,
+    attempts: Vec<(String, Feedback)>,
+}
+
+impl Ledger {
+    /// The ledger for `part` lives next to the day's input.
+    pub fn path(input_file: &str, part: u8) -> PathBuf {
+        let dir = Path::new(input_file).parent().unwrap_or(Path::new(""));
+        dir.join(format!("ledger-part{part}"))
+    }
+
+    /// Read the ledger at `path`; a missing file is an empty ledger.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut attempts = Vec::new();
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (answer, feedback) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| format!("{}: invalid line: {line}", path.display()))?;
+
+            attempts.push((answer.to_owned(), feedback.trim().parse()?));
+        }
+
+        Ok(Self { path, attempts })
+    }
+
+    pub fn attempts(&self) -> &[(String, Feedback)] {
+        &self.attempts
+    }
+
+    /// Append an attempt to the ledger file.
+    pub fn record(&mut self, answer: &str, feedback: Feedback) -> Result<()> {
+        use std::io::Write;
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{answer} {feedback}")?;
+
+        self.attempts.push((answer.to_owned(), feedback));
+        Ok(())
+    }
+
+    pub fn verdict(&self, answer: &str) -> Option<Verdict> {
+        if let Some((_, feedback)) = self.attempts.iter().find(|(a, _)| a == answer) {
+            return match feedback {
+                Feedback::Correct => Some(Verdict::Correct),
+                feedback => Some(Verdict::KnownWrong(*feedback)),
+            };
+        }
+
+        for (attempt, feedback) in self.attempts.iter() {
+            match (feedback, compare(answer, attempt)) {
+                (Feedback::Correct, _) => return Some(Verdict::NotCorrect(attempt.clone())),
+                (Feedback::TooHigh, Some(Ordering::Greater)) => {
+                    return Some(Verdict::TooHigh(attempt.clone()));
+                }
+                (Feedback::TooLow, Some(Ordering::Less)) => {
+                    return Some(Verdict::TooLow(attempt.clone()));
+                }
+                _ => (),
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ledger(attempts: &[(&str, Feedback)]) -> Ledger {
+        Ledger {
+            path: PathBuf::new(),
+            attempts: attempts
+                .iter()
+                .map(|(answer, feedback)| (answer.to_string(), *feedback))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn verdicts() {
+        let ledger = ledger(&[
+            ("100", Feedback::TooHigh),
+            ("20", Feedback::TooLow),
+            ("50", Feedback::Wrong),
+        ]);
+
+        assert_eq!(
+            ledger.verdict("50"),
+            Some(Verdict::KnownWrong(Feedback::Wrong))
+        );
+        assert_eq!(
+            ledger.verdict("100"),
+            Some(Verdict::KnownWrong(Feedback::TooHigh))
+        );
+        assert_eq!(
+            ledger.verdict("150"),
+            Some(Verdict::TooHigh("100".to_owned()))
+        );
+        assert_eq!(ledger.verdict("10"), Some(Verdict::TooLow("20".to_owned())));
+        assert_eq!(ledger.verdict("60"), None);
+    }
+
+    #[test]
+    fn correct() {
+        let ledger = ledger(&[("100", Feedback::TooHigh), ("42", Feedback::Correct)]);
+
+        assert_eq!(ledger.verdict("42"), Some(Verdict::Correct));
+        assert_eq!(
+            ledger.verdict("43"),
+            Some(Verdict::NotCorrect("42".to_owned()))
+        );
+    }
+
+    #[test]
+    fn load_and_record() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("aoc2025-ledger-{}", std::process::id()));
+        let path = Ledger::path(dir.join("input").to_str().unwrap(), 1);
+
+        let mut ledger = Ledger::load(&path)?;
+        assert!(ledger.attempts().is_empty());
+        ledger.record("1234", Feedback::TooLow)?;
+        ledger.record("2345", Feedback::Correct)?;
+
+        let ledger = Ledger::load(&path)?;
+        std::fs::remove_dir_all(&dir)?;
+
+        assert_eq!(
+            ledger.attempts(),
+            [
+                ("1234".to_owned(), Feedback::TooLow),
+                ("2345".to_owned(), Feedback::Correct)
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+// Parsing and solving need only `core` and `alloc`; file IO and the runner sit behind `std`.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod anonymize;
+pub mod bitgrid;
+#[cfg(feature = "std")]
+pub mod cache;
+pub mod checked;
+#[cfg(feature = "day01")]
+pub mod day01;
+#[cfg(feature = "day02")]
+pub mod day02;
+#[cfg(feature = "day03")]
+pub mod day03;
+#[cfg(feature = "day04")]
+pub mod day04;
+#[cfg(feature = "day05")]
+pub mod day05;
+#[cfg(feature = "day06")]
+pub mod day06;
+#[cfg(feature = "day07")]
+pub mod day07;
+#[cfg(feature = "day08")]
+pub mod day08;
+#[cfg(feature = "day09")]
+pub mod day09;
+#[cfg(feature = "std")]
+pub mod diff;
+pub mod disjoint_set;
+pub mod encode;
+pub mod generate;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod hash;
+#[cfg(feature = "std")]
+pub mod import;
+pub mod input;
+pub mod intervals;
+#[cfg(feature = "std")]
+pub mod ledger;
+pub mod parse;
+pub mod puzzle;
+#[cfg(feature = "std")]
+pub mod registry;
+#[cfg(feature = "std")]
+pub mod render;
+#[cfg(feature = "std")]
+pub mod runner;
+pub mod simulation;
+
+pub type Error = alloc::boxed::Box<dyn core::error::Error>;
+pub type Result<T> = core::result::Result<T, Error>;
+pub use puzzle::{Puzzle, ZeroCopy};
+
+pub mod prelude {
+    pub use super::Result;
+    pub use crate::input::Layout;
+    pub use crate::puzzle::Puzzle;
+    pub use alloc::borrow::ToOwned;
+    pub use alloc::boxed::Box;
+    pub use alloc::string::{String, ToString};
+    pub use alloc::vec::Vec;
+    pub use alloc::{format, vec};
+}
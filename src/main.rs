@@ -1,77 +1,155 @@
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod puzzle;
-
-macro_rules! day_impl {
-    ($mod:ident, $one:ident, $two:ident) => {
-        let mut one = $mod::$one;
-        let start = std::time::Instant::now();
-        let solution = one.solution()?;
-        println!("part one: {solution} (took {:?})", start.elapsed());
-
-        let mut two = $mod::$two;
-        let start = std::time::Instant::now();
-        let solution = two.solution()?;
-        println!("part two: {solution} (took {:?})", start.elapsed());
-    };
+mod args;
+
+use aoc2025::generate::Rng;
+use aoc2025::ledger::{Feedback, Ledger};
+use aoc2025::registry::{self, Day};
+use aoc2025::runner::{self, CacheMode, Runner, StepMode};
+use aoc2025::{Result, anonymize, diff, import};
+use args::Args;
+use std::path::PathBuf;
+
+/// The day given on the command line, or all of them.
+fn days(args: &mut Args) -> Result<Vec<&'static Day>> {
+    match args.positional() {
+        Some(day) => Ok(vec![registry::get(day.parse()?)?]),
+        None => Ok(registry::DAYS.iter().collect()),
+    }
 }
 
-macro_rules! day_fun {
-    ($name:ident, $one:ident, $two:ident) => {
-        fn $name() -> Result<()> {
-            day_impl!($name, $one, $two);
+fn cache_mode(args: &mut Args) -> Result<CacheMode> {
+    match (args.flag("--cached"), args.flag("--no-cache")) {
+        (false, false) => Ok(CacheMode::Write),
+        (true, false) => Ok(CacheMode::Read),
+        (false, true) => Ok(CacheMode::Off),
+        (true, true) => Err("--cached and --no-cache are mutually exclusive".into()),
+    }
+}
+
+fn main() -> Result<()> {
+    let mut args = Args::from_env();
+    let command = args.positional().ok_or("specify a day!")?;
+
+    match command.as_str() {
+        "anonymize" => {
+            let seed = args.option("--seed")?.unwrap_or(0);
+            let day = registry::get(args.positional().ok_or("specify a day!")?.parse()?)?;
+            args.finish()?;
+
+            anonymize::write_fixture(day, seed)
+        }
+        "check" => {
+            let days = days(&mut args)?;
+            args.finish()?;
+            runner::check_days(days)
+        }
+        "diff" => {
+            let days = days(&mut args)?;
+            args.finish()?;
+            diff::diff_days(days)
+        }
+        "gen" => {
+            let seed = args.option("--seed")?.unwrap_or(0);
+            let size = args.option("--size")?.unwrap_or(100);
+            let day = registry::get(args.positional().ok_or("specify a day!")?.parse()?)?;
+            args.finish()?;
+
+            print!("{}", (day.generate)(&mut Rng::new(seed), size));
             Ok(())
         }
-    };
-}
+        "import-examples" => {
+            let force = args.flag("--force");
+            let day: u32 = args.positional().ok_or("specify a day!")?.parse()?;
+            let page = args.positional().ok_or("specify a saved puzzle page!")?;
+            args.finish()?;
 
-pub type Error = Box<dyn std::error::Error>;
-pub type Result<T> = std::result::Result<T, Error>;
-pub use puzzle::Puzzle;
+            if !(1..=12).contains(&day) {
+                return Err("invalid day".into());
+            }
 
-pub mod prelude {
-    pub use super::Result;
-    pub use crate::puzzle::Puzzle;
-    pub use std::fs::File;
-    pub use std::io::{BufRead, BufReader};
-}
+            import::import_examples(day, page.as_ref(), force)
+        }
+        "ledger" => {
+            let day = registry::get(args.positional().ok_or("specify a day!")?.parse()?)?;
 
-fn main() -> Result<()> {
-    let day = std::env::args()
-        .nth(1)
-        .ok_or("specify a day!")?
-        .parse::<u32>()?;
-
-    match day {
-        1 => day01(),
-        2 => day02(),
-        3 => day03(),
-        4 => day04(),
-        5 => day05(),
-        6 => day06(),
-        7 => day07(),
-        8 => day08(),
-        9 => day09(),
-        10 => todo!(),
-        11 => todo!(),
-        12 => todo!(),
-        _ => Err("invalid day".into()),
+            match args.positional() {
+                Some(part) => {
+                    let part = part.parse()?;
+                    let answer = args.positional().ok_or("specify an answer!")?;
+                    let feedback: Feedback =
+                        args.positional().ok_or("specify feedback!")?.parse()?;
+                    args.finish()?;
+
+                    Ledger::load(Ledger::path((day.input_file)(), part))?.record(&answer, feedback)
+                }
+                None => {
+                    args.finish()?;
+
+                    for part in 1..=2 {
+                        let ledger = Ledger::load(Ledger::path((day.input_file)(), part))?;
+
+                        for (answer, feedback) in ledger.attempts() {
+                            println!("part {part}: {answer} {feedback}");
+                        }
+                    }
+
+                    Ok(())
+                }
+            }
+        }
+        "simulate" => {
+            let part = args.option("--part")?;
+            let to = args.option("--to")?;
+            let trace = args.flag("--trace");
+            let interactive = args.flag("--interactive");
+            let day = registry::get(args.positional().ok_or("specify a day!")?.parse()?)?;
+            args.finish()?;
+
+            let mode = match (to, trace, interactive) {
+                (None, false, false) => StepMode::Finish,
+                (Some(n), false, false) => StepMode::To(n),
+                (None, true, false) => StepMode::Trace,
+                (None, false, true) => StepMode::Interactive,
+                _ => return Err("--to, --trace and --interactive are mutually exclusive".into()),
+            };
+
+            runner::simulate(day, part, mode)
+        }
+        "all" => {
+            let mut runner = Runner::new(cache_mode(&mut args)?)?;
+            args.finish()?;
+
+            let mut failures = 0;
+
+            for day in registry::DAYS {
+                println!("day {}:", day.number);
+
+                if let Err(e) = (day.run)(&mut runner, day) {
+                    println!("error: {e}");
+                    failures += 1;
+                }
+            }
+
+            runner.finish()?;
+
+            match failures {
+                0 => Ok(()),
+                n => Err(format!("{n} day(s) failed").into()),
+            }
+        }
+        day => {
+            let day = registry::get(day.parse()?)?;
+            let render: Option<PathBuf> = args.option("--render")?;
+            let mut runner = Runner::new(cache_mode(&mut args)?)?;
+            runner.explain(args.flag("--explain"));
+            args.finish()?;
+
+            (day.run)(&mut runner, day)?;
+            runner.finish()?;
+
+            match render {
+                Some(path) => (day.render)(&path),
+                None => Ok(()),
+            }
+        }
     }
 }
-
-day_fun!(day01, One, Two);
-day_fun!(day02, One, Two);
-day_fun!(day03, One, Two);
-day_fun!(day04, One, Two);
-day_fun!(day05, One, Two);
-day_fun!(day06, One, Two);
-day_fun!(day07, One, Two);
-day_fun!(day08, One, Two);
-day_fun!(day09, One, Two);
@@ -0,0 +1,320 @@
+//! A small scanner for the line-oriented puzzle inputs, reporting where parsing went wrong.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::{self, FromStr, Utf8Error};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: Option<char>,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+
+        match self.found {
+            Some('\n') => write!(f, "end of line"),
+            Some(c) => write!(f, "{c:?}"),
+            None => write!(f, "end of input"),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+pub type Result<T> = core::result::Result<T, ParseError>;
+
+/// A whole input as text, so it can be scanned across line boundaries.
+pub fn text(input: &[u8]) -> core::result::Result<&str, Utf8Error> {
+    str::from_utf8(input)
+}
+
+/// Cursor over a whole input. Parsed items may borrow from the text, and the 1-based line and
+/// column of the next character are only worked out when an error needs them.
+#[derive(Debug, Clone)]
+pub struct Scanner<'a> {
+    text: &'a str,
+    offset: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self { text, offset: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.offset..]
+    }
+
+    pub fn position(&self) -> (usize, usize) {
+        let consumed = &self.text[..self.offset];
+        let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);
+
+        (
+            consumed.matches('\n').count() + 1,
+            consumed[line_start..].chars().count() + 1,
+        )
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    pub fn is_eof(&self) -> bool {
+        self.offset == self.text.len()
+    }
+
+    /// True at a newline or the end of input.
+    pub fn is_line_end(&self) -> bool {
+        matches!(self.rest().as_bytes().first(), None | Some(b'\n'))
+    }
+
+    /// An error at the current position.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        let (line, column) = self.position();
+
+        ParseError {
+            line,
+            column,
+            expected: expected.into(),
+            found: self.peek(),
+        }
+    }
+
+    pub fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.offset += c.len_utf8();
+
+        Some(c)
+    }
+
+    /// Consumes `c` if it is next.
+    pub fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.offset += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn expect(&mut self, c: char) -> Result<()> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(format!("{c:?}")))
+        }
+    }
+
+    /// Skips spaces and tabs, but not newlines.
+    pub fn skip_spaces(&mut self) {
+        self.take_while(|c| c == ' ' || c == '\t');
+    }
+
+    /// Consumes the characters matching `pred` and returns them.
+    pub fn take_while(&mut self, pred: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !pred(c)).unwrap_or(rest.len());
+        self.offset += len;
+
+        &rest[..len]
+    }
+
+    pub fn digit(&mut self) -> Result<u8> {
+        match self.peek().and_then(|c| c.to_digit(10)) {
+            Some(digit) => {
+                self.bump();
+                Ok(digit as u8)
+            }
+            None => Err(self.error("digit")),
+        }
+    }
+
+    /// A run of decimal digits, without a sign.
+    pub fn unsigned<T: FromStr>(&mut self) -> Result<T> {
+        let start = self.clone();
+        let digits = self.take_while(|c| c.is_ascii_digit());
+
+        Self::number(start, digits)
+    }
+
+    /// A run of decimal digits with an optional leading `-`.
+    pub fn integer<T: FromStr>(&mut self) -> Result<T> {
+        let start = self.clone();
+
+        self.eat('-');
+
+        if self.take_while(|c| c.is_ascii_digit()).is_empty() {
+            return Err(self.error("digit"));
+        }
+
+        let digits = &start.text[start.offset..self.offset];
+
+        Self::number(start, digits)
+    }
+
+    fn number<T: FromStr>(start: Self, text: &str) -> Result<T> {
+        if text.is_empty() {
+            return Err(start.error("integer"));
+        }
+
+        text.parse()
+            .map_err(|_| start.error(format!("integer fitting {}", core::any::type_name::<T>())))
+    }
+
+    /// Exactly `N` items separated by `separator`.
+    pub fn tuple<T, const N: usize>(
+        &mut self,
+        separator: char,
+        mut item: impl FnMut(&mut Self) -> Result<T>,
+    ) -> Result<[T; N]> {
+        let mut error = None;
+
+        let items = core::array::from_fn(|i| {
+            if error.is_some() {
+                return None;
+            }
+
+            let parsed = match i {
+                0 => item(self),
+                _ => self.expect(separator).and_then(|()| item(self)),
+            };
+
+            parsed.map_err(|e| error = Some(e)).ok()
+        });
+
+        match error {
+            Some(e) => Err(e),
+            None => Ok(items.map(|item| item.expect("every item was parsed"))),
+        }
+    }
+
+    /// One or more items on the current line separated by `separator`, which may also trail.
+    pub fn separated<T>(
+        &mut self,
+        separator: char,
+        mut item: impl FnMut(&mut Self) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        let mut items = vec![item(self)?];
+
+        while self.eat(separator) && !self.is_line_end() {
+            items.push(item(self)?);
+        }
+
+        Ok(items)
+    }
+
+    /// Consumes the end of the current line, or accepts the end of input.
+    pub fn end_of_line(&mut self) -> Result<()> {
+        if self.eat('\n') || self.is_eof() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+
+    /// One `item` per line, up to a blank line or the end of input, neither of which is consumed.
+    pub fn lines<T>(&mut self, mut item: impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+
+        while !self.is_line_end() {
+            items.push(item(self)?);
+            self.end_of_line()?;
+        }
+
+        Ok(items)
+    }
+
+    /// Sections separated by a single blank line, each parsed by `section`.
+    pub fn sections<T>(
+        &mut self,
+        mut section: impl FnMut(&mut Self) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        let mut sections = vec![section(self)?];
+
+        while self.eat('\n') {
+            sections.push(section(self)?);
+        }
+
+        Ok(sections)
+    }
+
+    /// Requires the end of input, allowing trailing whitespace.
+    pub fn finish(mut self) -> Result<()> {
+        self.take_while(char::is_whitespace);
+
+        if self.is_eof() {
+            Ok(())
+        } else {
+            Err(self.error("end of input"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers() -> Result<()> {
+        let mut s = Scanner::new("12,-34,x");
+
+        assert_eq!(s.unsigned::<u64>()?, 12);
+        s.expect(',')?;
+        assert_eq!(s.integer::<i32>()?, -34);
+        s.expect(',')?;
+
+        let err = s.unsigned::<u64>().unwrap_err();
+        assert_eq!((err.line, err.column, err.found), (1, 8, Some('x')));
+
+        assert!(Scanner::new("-5").unsigned::<u32>().is_err());
+        assert!(Scanner::new("-").integer::<i32>().is_err());
+        assert!(Scanner::new("256").unsigned::<u8>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn lists() -> Result<()> {
+        let mut s = Scanner::new("1-2,3-4,\n5-6\n");
+
+        let ranges =
+            s.lines(|s| s.separated(',', |s| s.tuple::<u32, 2>('-', Scanner::unsigned)))?;
+
+        assert_eq!(ranges, vec![vec![[1, 2], [3, 4]], vec![[5, 6]]]);
+        s.finish()
+    }
+
+    #[test]
+    fn sections() -> Result<()> {
+        let s = &mut Scanner::new("1\n2\n\n3\n");
+        let sections = s.sections(|s| s.lines(Scanner::unsigned::<u8>))?;
+
+        assert_eq!(sections, vec![vec![1, 2], vec![3]]);
+        Ok(())
+    }
+
+    #[test]
+    fn positions() {
+        let mut s = Scanner::new("1,2,3\n4,5\n");
+        let err = s
+            .lines(|s| s.tuple::<u8, 3>(',', Scanner::unsigned))
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 4: expected ',', found end of line"
+        );
+    }
+}
@@ -1,12 +1,26 @@
 use crate::Result;
-use std::fmt::Debug;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+#[cfg(feature = "std")]
+use crate::input;
+use crate::input::Layout;
+#[cfg(feature = "std")]
+use crate::render::Scene;
+use crate::simulation::DynSimulation;
+use alloc::string::String;
+use core::fmt::Debug;
+use core::time::Duration;
 
 pub trait Puzzle {
     type Input;
     type Output;
 
+    /// Whether the day's parser is whitespace-sensitive; see [`crate::input::normalize`].
+    const LAYOUT: Layout;
+
+    /// How long normalizing, parsing and solving the real input should take in a release
+    /// build: about twice the measured time, but at least five milliseconds so timer noise
+    /// and a cold cache can't fail fast parts. Enforced by the ignored `budgets` test.
+    const BUDGET: Duration = Duration::from_secs(1);
+
     fn example_input() -> Self::Input;
     fn example_output() -> Self::Output;
 
@@ -24,20 +38,58 @@ pub trait Puzzle {
 
     fn input_file() -> &'static str;
 
-    fn load_input() -> Result<BufReader<File>> {
-        let f = File::open(Self::input_file())?;
-        Ok(BufReader::new(f))
+    #[cfg(feature = "std")]
+    fn load_input() -> Result<String> {
+        let text = input::read(Self::input_file())?;
+        Ok(input::normalize(&text, Self::LAYOUT))
     }
 
-    fn parse_input<B>(reader: B) -> Result<Self::Input>
-    where
-        B: BufRead;
+    /// Parses a whole normalized input. Only needs `core` and `alloc`, so solvers can run
+    /// without `std`.
+    fn parse_input(input: &[u8]) -> Result<Self::Input>;
 
     fn solve(&mut self, input: Self::Input) -> Result<Self::Output>;
 
+    /// Structural statistics about a parsed input, printed by the `check` command.
+    fn summary(_input: &Self::Input) -> Option<String> {
+        None
+    }
+
+    /// A picture of the puzzle state, written by the `--render` flag.
+    #[cfg(feature = "std")]
+    fn render(_input: &Self::Input) -> Option<Scene> {
+        None
+    }
+
+    /// The solver as a step-by-step simulation, for the `simulate` command.
+    fn simulation(_input: Self::Input) -> Option<DynSimulation> {
+        None
+    }
+
+    /// A human-readable derivation of the answer, printed by the `--explain` flag. Only called
+    /// then, so the plain answer path pays nothing for it.
+    fn explain(&mut self, _input: Self::Input) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    #[cfg(feature = "std")]
     fn solution(&mut self) -> Result<Self::Output> {
-        let reader = Self::load_input()?;
-        let input = Self::parse_input(reader)?;
+        let text = Self::load_input()?;
+        let input = Self::parse_input(text.as_bytes())?;
         self.solve(input)
     }
 }
+
+/// An alternative entry point for a day whose parsed input can borrow from the input buffer,
+/// read into memory once, rather than copy out of it.
+///
+/// The runner keeps to [`Puzzle`], whose owned input can be stored; see `benches/parse.rs` for
+/// what borrowing saves.
+pub trait ZeroCopy: Puzzle {
+    /// The parsed input, borrowing from the text it was parsed from.
+    type Borrowed<'a>;
+
+    fn parse_borrowed(input: &[u8]) -> Result<Self::Borrowed<'_>>;
+
+    fn solve_borrowed(&mut self, input: Self::Borrowed<'_>) -> Result<Self::Output>;
+}
@@ -0,0 +1,187 @@
+use crate::Result;
+use crate::generate::Rng;
+use crate::hash::Fnv1a;
+use crate::runner::Runner;
+use crate::simulation::DynSimulation;
+use core::hash::Hasher;
+use std::path::Path;
+use std::time::Duration;
+
+/// One solver for one part of a day. A part may have several, e.g. a naive version kept as a
+/// reference for an optimized one; see the `diff` command.
+pub struct Implementation {
+    pub part: u8,
+    pub name: &'static str,
+    pub solve: fn(&str) -> Result<String>,
+}
+
+pub struct Day {
+    pub number: u32,
+    pub run: fn(&mut Runner, &Day) -> Result<()>,
+    /// Per part, as a part may parse the input its own way, e.g. day 6 `Two` reads columns.
+    pub checks: [fn() -> Result<Option<String>>; 2],
+    pub render: fn(&Path) -> Result<()>,
+    pub simulations: [fn() -> Result<Option<DynSimulation>>; 2],
+    pub input_file: fn() -> &'static str,
+    pub example: &'static str,
+    pub generate: fn(&mut Rng, usize) -> String,
+    pub anonymize: fn(&str, &mut Rng) -> Result<String>,
+    pub implementations: &'static [Implementation],
+    /// The main solvers' [`crate::Puzzle::BUDGET`]s, by part.
+    pub budgets: [Duration; 2],
+    /// The day's source code, standing in for a version of its solvers.
+    pub source: &'static str,
+}
+
+impl Day {
+    /// Changes whenever the day's solvers, the modules they share with other days, the crate
+    /// version, or whether arithmetic is `checked` do.
+    pub fn solver_hash(&self) -> u64 {
+        let mut hasher = Fnv1a::default();
+        hasher.write(env!("CARGO_PKG_VERSION").as_bytes());
+        // a checked build errors where an unchecked one may have cached a wrapped answer
+        hasher.write(&[u8::from(cfg!(feature = "checked"))]);
+
+        for source in SHARED_SOURCES.iter().chain([&self.source]) {
+            hasher.write(b"\n");
+            hasher.write(source.as_bytes());
+        }
+
+        hasher.finish()
+    }
+
+    pub fn implementations(&self, part: u8) -> impl Iterator<Item = &Implementation> {
+        self.implementations
+            .iter()
+            .filter(move |imp| imp.part == part)
+    }
+}
+
+/// The modules the days parse and solve with, so a fix in any of them invalidates cached
+/// answers and stored parses.
+const SHARED_SOURCES: &[&str] = &[
+    include_str!("bitgrid.rs"),
+    include_str!("checked.rs"),
+    include_str!("disjoint_set.rs"),
+    include_str!("encode.rs"),
+    include_str!("geometry.rs"),
+    include_str!("graph.rs"),
+    include_str!("grid.rs"),
+    include_str!("input.rs"),
+    include_str!("intervals.rs"),
+    include_str!("parse.rs"),
+    include_str!("puzzle.rs"),
+    include_str!("simulation.rs"),
+];
+
+// unused only when every day feature is disabled
+#[allow(unused_macros)]
+macro_rules! day {
+    ($number:literal, $mod:ident $(, $part:literal => $name:literal: $alt:ident)*) => {
+        Day {
+            number: $number,
+            run: |runner, day| runner.run(day, crate::$mod::One, crate::$mod::Two),
+            checks: [
+                crate::runner::check::<crate::$mod::One>,
+                crate::runner::check::<crate::$mod::Two>,
+            ],
+            render: crate::runner::render::<crate::$mod::One>,
+            simulations: [
+                crate::runner::simulation::<crate::$mod::One>,
+                crate::runner::simulation::<crate::$mod::Two>,
+            ],
+            input_file: <crate::$mod::One as crate::Puzzle>::input_file,
+            example: crate::$mod::EXAMPLE,
+            generate: <crate::$mod::One as crate::generate::Generator>::generate,
+            anonymize: crate::anonymize::anonymize_text::<crate::$mod::One>,
+            implementations: &[
+                Implementation {
+                    part: 1,
+                    name: "main",
+                    solve: |text| crate::runner::solve_text(crate::$mod::One, text),
+                },
+                Implementation {
+                    part: 2,
+                    name: "main",
+                    solve: |text| crate::runner::solve_text(crate::$mod::Two, text),
+                },
+                $(
+                    Implementation {
+                        part: $part,
+                        name: $name,
+                        solve: |text| crate::runner::solve_text(crate::$mod::$alt, text),
+                    },
+                )*
+            ],
+            budgets: [
+                <crate::$mod::One as crate::Puzzle>::BUDGET,
+                <crate::$mod::Two as crate::Puzzle>::BUDGET,
+            ],
+            source: include_str!(concat!(stringify!($mod), ".rs")),
+        }
+    };
+}
+
+/// The days compiled in; each is behind a cargo feature named after its module.
+pub const DAYS: &[Day] = &[
+    #[cfg(feature = "day01")]
+    day!(1, day01),
+    #[cfg(feature = "day02")]
+    day!(2, day02, 1 => "naive": NaiveOne, 2 => "naive": NaiveTwo),
+    #[cfg(feature = "day03")]
+    day!(3, day03),
+    #[cfg(feature = "day04")]
+    day!(4, day04),
+    #[cfg(feature = "day05")]
+    day!(5, day05, 2 => "naive": NaiveTwo),
+    #[cfg(feature = "day06")]
+    day!(6, day06),
+    #[cfg(feature = "day07")]
+    day!(7, day07, 2 => "sweep": SweepTwo),
+    #[cfg(feature = "day08")]
+    day!(8, day08),
+    #[cfg(feature = "day09")]
+    day!(9, day09, 2 => "naive": NaiveTwo),
+];
+
+/// Every day that has a module, whether or not its feature is enabled.
+const IMPLEMENTED: &[u32] = &[1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+pub fn get(number: u32) -> Result<&'static Day> {
+    match DAYS.iter().find(|day| day.number == number) {
+        Some(day) => Ok(day),
+        None if IMPLEMENTED.contains(&number) => {
+            Err(format!("day {number} not compiled in; enable the day{number:02} feature").into())
+        }
+        None if (1..=12).contains(&number) => {
+            Err(format!("day {number} not implemented yet").into())
+        }
+        None => Err("invalid day".into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every solver gives the same answer for its example saved with a BOM and CRLF line ends.
+    #[test]
+    fn crlf() -> Result<()> {
+        for day in DAYS {
+            let text = format!("\u{feff}{}", day.example.replace('\n', "\r\n"));
+
+            for imp in day.implementations {
+                assert_eq!(
+                    (imp.solve)(&text)?,
+                    (imp.solve)(day.example)?,
+                    "day {} part {} ({})",
+                    day.number,
+                    imp.part,
+                    imp.name
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
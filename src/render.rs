@@ -0,0 +1,373 @@
+//! Pictures of puzzle state, drawn as colored ASCII for the terminal, binary PPM or SVG.
+
+use crate::Result;
+use crate::geometry::{Bounds2, Point2};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Longest side of a PPM image, in pixels.
+const PPM_SIZE: usize = 1000;
+
+/// Longest side of an ASCII picture, in characters; smaller scenes are drawn one cell per
+/// character.
+const ASCII_SIZE: usize = 160;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Self = Self::new(0, 0, 0);
+    pub const WHITE: Self = Self::new(255, 255, 255);
+    pub const GREY: Self = Self::new(128, 128, 128);
+    pub const RED: Self = Self::new(220, 50, 47);
+    pub const GREEN: Self = Self::new(133, 153, 0);
+    pub const YELLOW: Self = Self::new(181, 137, 0);
+    pub const BLUE: Self = Self::new(38, 139, 210);
+    pub const CYAN: Self = Self::new(42, 161, 152);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// The color a fraction `t` of the way from `self` to `other`.
+    pub fn mix(self, other: Self, t: f64) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+
+        Self::new(
+            channel(self.r, other.r),
+            channel(self.g, other.g),
+            channel(self.b, other.b),
+        )
+    }
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    /// A filled unit square, drawn as `glyph` in ASCII.
+    Cell(Point2<u64>, char),
+    Line(Point2<u64>, Point2<u64>),
+    /// An outline.
+    Rect(Bounds2<u64>),
+    Point(Point2<u64>),
+}
+
+/// Shapes on a `width` by `height` canvas, drawn in order so later ones end up on top.
+#[derive(Debug, Clone)]
+pub struct Scene {
+    width: u64,
+    height: u64,
+    shapes: Vec<(Shape, Color)>,
+}
+
+impl Scene {
+    pub fn new(width: u64, height: u64) -> Self {
+        Self {
+            width,
+            height,
+            shapes: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, shape: Shape, color: Color) {
+        self.shapes.push((shape, color));
+    }
+
+    pub fn cell(&mut self, x: u64, y: u64, glyph: char, color: Color) {
+        self.add(Shape::Cell(Point2::new(x, y), glyph), color);
+    }
+
+    pub fn line(&mut self, a: Point2<u64>, b: Point2<u64>, color: Color) {
+        self.add(Shape::Line(a, b), color);
+    }
+
+    pub fn rect(&mut self, bounds: Bounds2<u64>, color: Color) {
+        self.add(Shape::Rect(bounds), color);
+    }
+
+    pub fn point(&mut self, at: Point2<u64>, color: Color) {
+        self.add(Shape::Point(at), color);
+    }
+
+    /// One line of text per row, with ANSI colors if `colored`.
+    pub fn ascii(&self, colored: bool) -> String {
+        let raster = Raster::new(self, ASCII_SIZE, 1.0);
+        let mut out = String::new();
+
+        for row in raster.pixels.chunks(raster.width) {
+            let mut current = None;
+
+            for pixel in row {
+                let color = pixel.map(|(_, color)| color);
+
+                if colored && color != current {
+                    match color {
+                        Some(c) => write!(out, "\x1b[38;2;{};{};{}m", c.r, c.g, c.b).unwrap(),
+                        None => out.push_str("\x1b[0m"),
+                    }
+
+                    current = color;
+                }
+
+                out.push(pixel.map_or(' ', |(glyph, _)| glyph));
+            }
+
+            if current.is_some() {
+                out.push_str("\x1b[0m");
+            }
+
+            out.push('\n');
+        }
+
+        out
+    }
+
+    /// A binary (P6) PPM image on a black background.
+    pub fn ppm(&self) -> Vec<u8> {
+        let raster = Raster::new(self, PPM_SIZE, f64::INFINITY);
+        let mut out = format!("P6\n{} {}\n255\n", raster.width, raster.height).into_bytes();
+
+        for pixel in raster.pixels {
+            let Color { r, g, b } = pixel.map_or(Color::BLACK, |(_, color)| color);
+            out.extend([r, g, b]);
+        }
+
+        out
+    }
+
+    /// An SVG document in scene coordinates, where lines and points sit on cell centres.
+    pub fn svg(&self) -> String {
+        let size = self.width.max(self.height).max(1) as f64;
+        let scale = 800.0 / size;
+        let radius = size / 200.0;
+
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\" width=\"{:.0}\" \
+             height=\"{:.0}\">\n<rect width=\"100%\" height=\"100%\" fill=\"black\"/>\n",
+            self.width,
+            self.height,
+            self.width as f64 * scale,
+            self.height as f64 * scale,
+        );
+
+        let centre = |v: u64| v as f64 + 0.5;
+        let stroke = "vector-effect=\"non-scaling-stroke\" stroke-width=\"1.5\"";
+
+        for (shape, color) in &self.shapes {
+            let color = color.hex();
+
+            match shape {
+                Shape::Cell(at, _) => writeln!(
+                    out,
+                    "<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"{color}\"/>",
+                    at.x, at.y
+                ),
+                Shape::Line(a, b) => writeln!(
+                    out,
+                    "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{color}\" {stroke}/>",
+                    centre(a.x),
+                    centre(a.y),
+                    centre(b.x),
+                    centre(b.y)
+                ),
+                Shape::Rect(Bounds2 { min, max }) => writeln!(
+                    out,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" \
+                     stroke=\"{color}\" {stroke}/>",
+                    centre(min.x),
+                    centre(min.y),
+                    max.x - min.x,
+                    max.y - min.y
+                ),
+                Shape::Point(at) => writeln!(
+                    out,
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"{radius}\" fill=\"{color}\"/>",
+                    centre(at.x),
+                    centre(at.y)
+                ),
+            }
+            .unwrap();
+        }
+
+        out.push_str("</svg>\n");
+        out
+    }
+
+    /// Writes the scene in the format given by the extension of `path`: `.ppm`, `.svg`, or
+    /// plain ASCII otherwise. A path of `-` prints colored ASCII instead.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if path == Path::new("-") {
+            print!("{}", self.ascii(true));
+            return Ok(());
+        }
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("ppm") => std::fs::write(path, self.ppm())?,
+            Some("svg") => std::fs::write(path, self.svg())?,
+            _ => std::fs::write(path, self.ascii(false))?,
+        }
+
+        Ok(())
+    }
+}
+
+/// A scene scaled so its longest side is `size` pixels, magnified at most `max_zoom` times.
+struct Raster {
+    width: usize,
+    height: usize,
+    zoom: f64,
+    pixels: Vec<Option<(char, Color)>>,
+}
+
+impl Raster {
+    fn new(scene: &Scene, size: usize, max_zoom: f64) -> Self {
+        let zoom = (size as f64 / scene.width.max(scene.height).max(1) as f64).min(max_zoom);
+        let width = ((scene.width as f64 * zoom).ceil() as usize).max(1);
+        let height = ((scene.height as f64 * zoom).ceil() as usize).max(1);
+
+        let mut raster = Self {
+            width,
+            height,
+            zoom,
+            pixels: vec![None; width * height],
+        };
+
+        for (shape, color) in &scene.shapes {
+            raster.draw(*shape, *color);
+        }
+
+        raster
+    }
+
+    /// The pixel containing the top left corner of cell `v`.
+    fn start(&self, v: u64, len: usize) -> usize {
+        ((v as f64 * self.zoom) as usize).min(len - 1)
+    }
+
+    /// The pixel containing the centre of cell `v`.
+    fn centre(&self, p: Point2<u64>) -> (usize, usize) {
+        let map = |v: u64, len: usize| (((v as f64 + 0.5) * self.zoom) as usize).min(len - 1);
+        (map(p.x, self.width), map(p.y, self.height))
+    }
+
+    fn plot(&mut self, x: usize, y: usize, glyph: char, color: Color) {
+        self.pixels[y * self.width + x] = Some((glyph, color));
+    }
+
+    /// Bresenham's line algorithm.
+    fn line(&mut self, from: (usize, usize), to: (usize, usize), glyph: char, color: Color) {
+        let (mut x, mut y) = (from.0 as isize, from.1 as isize);
+        let (x1, y1) = (to.0 as isize, to.1 as isize);
+        let (dx, dy) = ((x1 - x).abs(), -(y1 - y).abs());
+        let (sx, sy) = ((x1 - x).signum(), (y1 - y).signum());
+        let mut error = dx + dy;
+
+        loop {
+            self.plot(x as usize, y as usize, glyph, color);
+
+            if (x, y) == (x1, y1) {
+                break;
+            }
+
+            if 2 * error >= dy {
+                error += dy;
+                x += sx;
+            }
+
+            if 2 * error <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    fn draw(&mut self, shape: Shape, color: Color) {
+        match shape {
+            Shape::Cell(at, glyph) => {
+                let (x0, y0) = (self.start(at.x, self.width), self.start(at.y, self.height));
+                let x1 = self.start(at.x + 1, self.width + 1).max(x0 + 1);
+                let y1 = self.start(at.y + 1, self.height + 1).max(y0 + 1);
+
+                for y in y0..y1 {
+                    for x in x0..x1 {
+                        self.plot(x, y, glyph, color);
+                    }
+                }
+            }
+            Shape::Line(a, b) => self.line(self.centre(a), self.centre(b), '*', color),
+            Shape::Rect(Bounds2 { min, max }) => {
+                let (x0, y0) = self.centre(min);
+                let (x1, y1) = self.centre(max);
+
+                self.line((x0, y0), (x1, y0), '+', color);
+                self.line((x1, y0), (x1, y1), '+', color);
+                self.line((x1, y1), (x0, y1), '+', color);
+                self.line((x0, y1), (x0, y0), '+', color);
+            }
+            Shape::Point(at) => {
+                let (x, y) = self.centre(at);
+                self.plot(x, y, 'o', color);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scene() -> Scene {
+        let mut scene = Scene::new(4, 3);
+        scene.cell(0, 0, '#', Color::RED);
+        scene.line(Point2::new(0, 2), Point2::new(3, 2), Color::GREEN);
+        scene.point(Point2::new(3, 0), Color::BLUE);
+        scene
+    }
+
+    #[test]
+    fn ascii() {
+        assert_eq!(scene().ascii(false), "#  o\n    \n****\n");
+        assert!(scene().ascii(true).contains("\x1b[38;2;220;50;47m#"));
+    }
+
+    #[test]
+    fn ppm() {
+        let ppm = scene().ppm();
+        let header = "P6\n1000 750\n255\n";
+
+        assert!(ppm.starts_with(header.as_bytes()));
+        assert_eq!(ppm.len(), header.len() + 1000 * 750 * 3);
+        assert_eq!(ppm[header.len()..header.len() + 3], [220, 50, 47]);
+    }
+
+    #[test]
+    fn svg() {
+        let svg = scene().svg();
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 4 3\""));
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"1\" height=\"1\" fill=\"#dc322f\"/>"));
+        assert!(svg.contains("<line x1=\"0.5\" y1=\"2.5\" x2=\"3.5\" y2=\"2.5\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn scaled_down() {
+        let mut scene = Scene::new(10_000, 10_000);
+        scene.rect(
+            Bounds2::new(Point2::new(0, 0), Point2::new(9_999, 9_999)),
+            Color::WHITE,
+        );
+
+        let ascii = scene.ascii(false);
+        assert_eq!(ascii.lines().count(), ASCII_SIZE);
+        assert!(ascii.lines().next().unwrap().chars().all(|c| c == '+'));
+    }
+}
@@ -0,0 +1,335 @@
+//! Running solvers against the real inputs and reporting on them.
+
+use crate::cache::{self, Cache, Entry, Key};
+use crate::encode::{self, Decode, Encode};
+use crate::hash::fnv1a;
+use crate::ledger::Ledger;
+use crate::registry::Day;
+use crate::simulation::{DynSimulation, Stepper};
+use crate::{Puzzle, Result, input};
+use std::fmt::Display;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Always solve, remembering the answers.
+    Write,
+    /// Use remembered answers where possible.
+    Read,
+    /// Always solve and leave the cache alone.
+    Off,
+}
+
+pub struct Runner {
+    mode: CacheMode,
+    cache: Cache,
+    explain: bool,
+}
+
+impl Runner {
+    pub fn new(mode: CacheMode) -> Result<Self> {
+        Ok(Self {
+            mode,
+            cache: Cache::load(cache::ANSWERS)?,
+            explain: false,
+        })
+    }
+
+    /// Print each part's derivation after its answer; see [`Puzzle::explain`].
+    pub fn explain(&mut self, explain: bool) {
+        self.explain = explain;
+    }
+
+    pub fn run<One, Two>(&mut self, day: &Day, one: One, two: Two) -> Result<()>
+    where
+        One: Puzzle,
+        Two: Puzzle,
+        One::Output: Display,
+        Two::Output: Display,
+        One::Input: Encode + for<'a> Decode<'a>,
+        Two::Input: Encode + for<'a> Decode<'a>,
+    {
+        self.part(day, 1, one)?;
+        self.part(day, 2, two)
+    }
+
+    fn part<P>(&mut self, day: &Day, part: u8, mut puzzle: P) -> Result<()>
+    where
+        P: Puzzle,
+        P::Output: Display,
+        P::Input: Encode + for<'a> Decode<'a>,
+    {
+        let name = if part == 1 { "one" } else { "two" };
+
+        let start = Instant::now();
+        let text = P::load_input()?;
+
+        let key = Key {
+            day: day.number,
+            part,
+            input: fnv1a(text.as_bytes()),
+            solver: day.solver_hash(),
+        };
+
+        let cached = match self.mode {
+            CacheMode::Read => self.cache.get(&key).cloned(),
+            CacheMode::Write | CacheMode::Off => None,
+        };
+
+        let (entry, note) = match cached {
+            Some(entry) => (entry, "cached, "),
+            None => {
+                let path = parsed_path(P::input_file(), part);
+                // an embedded input has no directory on disk to store its parse in
+                let store = self.mode != CacheMode::Off && !input::is_embedded(P::input_file());
+                let stored = if store {
+                    std::fs::read(&path).ok()
+                } else {
+                    None
+                };
+
+                let (input, note) = match stored
+                    .as_deref()
+                    .and_then(|bytes| encode::load(bytes, key.solver, key.input))
+                {
+                    Some(input) => (input, "stored parse, "),
+                    None => {
+                        let input = P::parse_input(text.as_bytes())?;
+
+                        if store {
+                            let bytes = encode::store(&input, key.solver, key.input);
+
+                            if let Err(e) = store_parsed(&path, &bytes) {
+                                println!(
+                                    "warning: can't store parsed input in {}: {e}",
+                                    path.display()
+                                );
+                            }
+                        }
+
+                        (input, "")
+                    }
+                };
+
+                let answer = puzzle.solve(input)?.to_string();
+                let entry = Entry {
+                    answer,
+                    elapsed: start.elapsed(),
+                };
+
+                if self.mode != CacheMode::Off {
+                    self.cache.insert(key, entry.clone());
+                }
+
+                (entry, note)
+            }
+        };
+
+        let ledger = Ledger::load(Ledger::path(P::input_file(), part))?;
+        let verdict = match ledger.verdict(&entry.answer) {
+            Some(verdict) => format!(" [{verdict}]"),
+            None => String::new(),
+        };
+
+        println!(
+            "part {name}: {} ({note}took {:?}){verdict}",
+            entry.answer, entry.elapsed
+        );
+
+        if self.explain {
+            let input = P::parse_input(text.as_bytes())?;
+
+            match puzzle.explain(input)? {
+                Some(explanation) => {
+                    for line in explanation.lines() {
+                        println!("  {line}");
+                    }
+                }
+                None => println!("  no explanation for this part"),
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn finish(self) -> Result<()> {
+        self.cache.save()
+    }
+}
+
+/// Storing a parse only saves time on later runs, so failing to is reported but not fatal.
+fn store_parsed(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    std::fs::write(path, bytes)
+}
+
+/// The parsed input for `part` is stored next to the day's input, so later runs can skip
+/// parsing it.
+pub fn parsed_path(input_file: &str, part: u8) -> PathBuf {
+    let dir = Path::new(input_file).parent().unwrap_or(Path::new(""));
+    dir.join(format!("parsed-part{part}"))
+}
+
+pub fn solve_text<P>(mut puzzle: P, text: &str) -> Result<String>
+where
+    P: Puzzle,
+    P::Output: Display,
+{
+    let text = input::normalize(text, P::LAYOUT);
+    let input = P::parse_input(text.as_bytes())?;
+    Ok(puzzle.solve(input)?.to_string())
+}
+
+pub fn check<P: Puzzle>() -> Result<Option<String>> {
+    let text = P::load_input()?;
+    let input = P::parse_input(text.as_bytes())?;
+    Ok(P::summary(&input))
+}
+
+pub fn render<P: Puzzle>(path: &Path) -> Result<()> {
+    let text = P::load_input()?;
+    let input = P::parse_input(text.as_bytes())?;
+
+    P::render(&input)
+        .ok_or("nothing to render for this day")?
+        .save(path)
+}
+
+pub fn simulation<P: Puzzle>() -> Result<Option<DynSimulation>> {
+    let text = P::load_input()?;
+    let input = P::parse_input(text.as_bytes())?;
+
+    Ok(P::simulation(input))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepMode {
+    /// Print only the final state.
+    Finish,
+    /// Print the state after `n` steps.
+    To(usize),
+    /// Print the state after every step.
+    Trace,
+    /// Print the state and wait for a command on stdin after every step.
+    Interactive,
+}
+
+fn show(stepper: &dyn Stepper) {
+    let state = stepper.show();
+    let state = state.trim_end();
+
+    if state.contains('\n') {
+        println!("step {}:\n{state}", stepper.steps());
+    } else {
+        println!("step {}: {state}", stepper.steps());
+    }
+}
+
+/// Steps through the simulation of the given part, or of the first part that has one.
+pub fn simulate(day: &Day, part: Option<u8>, mode: StepMode) -> Result<()> {
+    let parts = match part {
+        Some(part @ 1..=2) => vec![part],
+        Some(_) => return Err("invalid part".into()),
+        None => vec![1, 2],
+    };
+
+    let mut stepper = None;
+
+    for part in parts {
+        if let Some(found) = (day.simulations[part as usize - 1])()? {
+            stepper = Some(found);
+            break;
+        }
+    }
+
+    let mut stepper = stepper.ok_or(format!("day {} has no simulation", day.number))?;
+
+    match mode {
+        StepMode::Finish => {
+            while !stepper.is_done() {
+                stepper.step();
+            }
+        }
+        StepMode::To(n) => {
+            while stepper.steps() < n && !stepper.is_done() {
+                stepper.step();
+            }
+        }
+        StepMode::Trace => {
+            while !stepper.is_done() {
+                show(stepper.as_ref());
+                stepper.step();
+            }
+        }
+        StepMode::Interactive => {
+            let mut lines = std::io::stdin().lock().lines();
+
+            while !stepper.is_done() {
+                show(stepper.as_ref());
+                println!("[enter] step, <n> jump to step n, q quit");
+
+                let Some(line) = lines.next() else { break };
+
+                match line?.trim() {
+                    "" => stepper.step(),
+                    "q" => return Ok(()),
+                    n => {
+                        let n: usize = n.parse()?;
+
+                        while stepper.steps() < n && !stepper.is_done() {
+                            stepper.step();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    show(stepper.as_ref());
+    Ok(())
+}
+
+pub fn check_days<'a>(days: impl IntoIterator<Item = &'a Day>) -> Result<()> {
+    let mut failures = 0;
+
+    for day in days {
+        let start = Instant::now();
+        let mut summaries = Vec::new();
+        let mut failed = false;
+
+        for (part, check) in (1..=2).zip(day.checks) {
+            match check() {
+                Ok(summary) => summaries.extend(summary),
+                Err(e) => {
+                    println!("day {} part {part}: error: {e}", day.number);
+                    failed = true;
+                }
+            }
+        }
+
+        if failed {
+            failures += 1;
+        } else {
+            let summary = if summaries.is_empty() {
+                "ok".to_owned()
+            } else {
+                summaries.join("; ")
+            };
+            println!(
+                "day {}: {summary} (parsed in {:?})",
+                day.number,
+                start.elapsed()
+            );
+        }
+    }
+
+    match failures {
+        0 => Ok(()),
+        n => Err(format!("{n} input(s) failed to parse").into()),
+    }
+}
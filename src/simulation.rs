@@ -0,0 +1,110 @@
+//! Puzzles that evolve a state step by step, so the state can be inspected along the way.
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use core::fmt::Display;
+
+pub trait Simulation {
+    type State: Display;
+
+    fn state(&self) -> &Self::State;
+
+    /// Advances by one step. Does nothing once the simulation is done.
+    fn step(&mut self);
+
+    fn is_done(&self) -> bool;
+
+    /// Number of steps taken so far.
+    fn steps(&self) -> usize;
+
+    fn run(&mut self) {
+        while !self.is_done() {
+            self.step();
+        }
+    }
+
+    /// Steps until `n` steps have been taken or the simulation is done.
+    fn run_to(&mut self, n: usize) {
+        while self.steps() < n && !self.is_done() {
+            self.step();
+        }
+    }
+}
+
+/// A [`Simulation`] with its state type erased, for the `simulate` command.
+pub trait Stepper {
+    fn step(&mut self);
+    fn is_done(&self) -> bool;
+    fn steps(&self) -> usize;
+    fn show(&self) -> String;
+}
+
+/// What [`crate::Puzzle::simulation`] returns. Naming the alias rather than the trait keeps
+/// [`Stepper`]'s methods from clashing with [`Simulation`]'s.
+pub type DynSimulation = Box<dyn Stepper>;
+
+impl<S: Simulation> Stepper for S {
+    fn step(&mut self) {
+        Simulation::step(self);
+    }
+
+    fn is_done(&self) -> bool {
+        Simulation::is_done(self)
+    }
+
+    fn steps(&self) -> usize {
+        Simulation::steps(self)
+    }
+
+    fn show(&self) -> String {
+        self.state().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Countdown {
+        state: u32,
+        steps: usize,
+    }
+
+    impl Simulation for Countdown {
+        type State = u32;
+
+        fn state(&self) -> &u32 {
+            &self.state
+        }
+
+        fn step(&mut self) {
+            if !Simulation::is_done(self) {
+                self.state -= 1;
+                self.steps += 1;
+            }
+        }
+
+        fn is_done(&self) -> bool {
+            self.state == 0
+        }
+
+        fn steps(&self) -> usize {
+            self.steps
+        }
+    }
+
+    #[test]
+    fn run() {
+        let mut countdown = Countdown { state: 5, steps: 0 };
+
+        countdown.run_to(2);
+        assert_eq!((*countdown.state(), Simulation::steps(&countdown)), (3, 2));
+
+        countdown.run_to(10);
+        assert_eq!((*countdown.state(), Simulation::steps(&countdown)), (0, 5));
+
+        let stepper: &mut dyn Stepper = &mut countdown;
+        stepper.step();
+        assert_eq!((stepper.show(), stepper.steps()), ("0".to_owned(), 5));
+    }
+}
@@ -0,0 +1,73 @@
+//! Fails when a day's main solver takes longer on its real input than its budget allows. The
+//! time covers the whole run from text, normalizing and parsing included, as the budgets do.
+//!
+//! Needs the real inputs under `inputs/`, so it is ignored by default. Run with
+//! `cargo test --release --test budgets -- --ignored --nocapture`. Set `AOC_BUDGET_TOLERANCE`
+//! to scale every budget, e.g. on slow machines; it defaults to 2.
+
+use aoc2025::registry::DAYS;
+use aoc2025::{Result, input};
+use std::time::{Duration, Instant};
+
+const RUNS: usize = 3;
+
+fn tolerance() -> Result<f64> {
+    match std::env::var("AOC_BUDGET_TOLERANCE") {
+        Ok(value) => Ok(value.parse()?),
+        Err(_) => Ok(2.0),
+    }
+}
+
+/// The fastest of a few runs, so one unlucky run doesn't fail the test.
+fn measure(solve: fn(&str) -> Result<String>, text: &str) -> Result<Duration> {
+    let mut best = Duration::MAX;
+
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        solve(text)?;
+        best = best.min(start.elapsed());
+    }
+
+    Ok(best)
+}
+
+#[test]
+#[ignore = "needs the real inputs; run in release"]
+fn within_budget() -> Result<()> {
+    let tolerance = tolerance()?;
+    let mut measured = 0;
+    let mut over = Vec::new();
+
+    for day in DAYS {
+        let Ok(text) = input::read((day.input_file)()) else {
+            println!("day {}: no input, skipped", day.number);
+            continue;
+        };
+
+        for (part, budget) in (1..=2).zip(day.budgets) {
+            let imp = day.implementations(part).next().ok_or("no solver")?;
+            let elapsed = measure(imp.solve, &text)?;
+            let allowed = budget.mul_f64(tolerance);
+            measured += 1;
+
+            let verdict = if elapsed > allowed { "OVER" } else { "ok" };
+            println!(
+                "day {} part {part}: {elapsed:>10.2?} of {budget:>8.2?} {verdict}",
+                day.number
+            );
+
+            if elapsed > allowed {
+                over.push(format!("day {} part {part}", day.number));
+            }
+        }
+    }
+
+    assert!(measured > 0, "no real inputs found under inputs/");
+    assert!(
+        over.is_empty(),
+        "over budget (x{tolerance}): {}",
+        over.join(", ")
+    );
+
+    Ok(())
+}